@@ -1,6 +1,9 @@
 use std::collections::HashSet;
 
-use adventofcode::common::grid::minmax::minmax_ivec2;
+use adventofcode::common::{
+    bitgrid::BitGrid,
+    grid::{directions::NEIGHBORS, minmax::minmax_ivec2},
+};
 use criterion::{criterion_group, criterion_main, Criterion};
 use glam::IVec2;
 
@@ -17,5 +20,46 @@ fn bench_minmax_ivec2(c: &mut Criterion) {
     });
 }
 
-criterion_group!(helpers, bench_minmax_ivec2);
+/// Positions for a step of Conway's game of life.
+fn life_positions() -> HashSet<IVec2> {
+    (0..100)
+        .flat_map(|x| (0..100).map(move |y| IVec2::new(x, y)))
+        .filter(|p| (p.x * 7 + p.y * 13) % 3 == 0)
+        .collect()
+}
+
+fn bench_life_hashset(c: &mut Criterion) {
+    c.bench_function("game of life step HashSet", |b| {
+        let cells = life_positions();
+        let bounds = IVec2::new(100, 100);
+
+        b.iter(|| {
+            (0..bounds.x)
+                .flat_map(|x| (0..bounds.y).map(move |y| IVec2::new(x, y)))
+                .filter(|p| {
+                    let count = NEIGHBORS
+                        .iter()
+                        .filter(|&&dir| cells.contains(&(*p + dir)))
+                        .count();
+                    count == 3 || (count == 2 && cells.contains(p))
+                })
+                .collect::<HashSet<_>>()
+        })
+    });
+}
+
+fn bench_life_bitgrid(c: &mut Criterion) {
+    c.bench_function("game of life step BitGrid", |b| {
+        let cells = BitGrid::from_positions(100, 100, life_positions());
+
+        b.iter(|| &(&cells & &cells.neighbor_counts(2..=2)) | &cells.neighbor_counts(3..=3))
+    });
+}
+
+criterion_group!(
+    helpers,
+    bench_minmax_ivec2,
+    bench_life_hashset,
+    bench_life_bitgrid
+);
 criterion_main!(helpers);
@@ -16,23 +16,20 @@ macro_rules! benchmark {
             let input_path = input::get_default_input_path(year, day, download_input).unwrap();
             output::disable_output();
 
+            let input = input::read_input(&input_path).unwrap();
+
             c.bench_function(format!("{} day {} parsing", year, day).as_str(), |b| {
-                b.iter(|| {
-                    let mut input = input::read_input(&input_path).unwrap();
-                    <$year::$day::Solution as DayParser>::with_input(&mut input)
-                })
+                b.iter(|| <$year::$day::Solution as DayParser>::with_input(&input))
             });
 
             c.bench_function(format!("{} day {} part 1", year, day).as_str(), |b| {
-                let mut input = input::read_input(&input_path).unwrap();
-                let solution = <$year::$day::Solution as DayParser>::with_input(&mut input);
+                let solution = <$year::$day::Solution as DayParser>::with_input(&input);
 
                 b.iter(|| solution.part1())
             });
 
             c.bench_function(format!("{} day {} part 2", year, day).as_str(), |b| {
-                let mut input = input::read_input(&input_path).unwrap();
-                let solution = <$year::$day::Solution as DayParser>::with_input(&mut input);
+                let solution = <$year::$day::Solution as DayParser>::with_input(&input);
 
                 b.iter(|| solution.part2())
             });
@@ -0,0 +1,127 @@
+use std::{env, fs, path::Path};
+
+/// Embeds the example inputs (`input/<year>/example/day<day>[_<suffix>].txt`) for the wasm UI and
+/// the answers of the puzzle inputs (from the `test_part<part>_input` tests) for the terminal UI.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    write_examples(&Path::new(&out_dir).join("examples.rs"));
+    write_answers(&Path::new(&out_dir).join("answers.rs"));
+}
+
+fn write_examples(out: &Path) {
+    let mut examples = vec![];
+    if env::var_os("CARGO_FEATURE_WASM").is_some() {
+        println!("cargo:rerun-if-changed=input");
+        for year_dir in fs::read_dir("input").into_iter().flatten().flatten() {
+            let Some(year) = year_dir
+                .file_name()
+                .to_str()
+                .and_then(|y| y.parse::<u16>().ok())
+            else {
+                continue;
+            };
+            let example_dir = year_dir.path().join("example");
+            for file in fs::read_dir(&example_dir).into_iter().flatten().flatten() {
+                let path = file.path();
+                let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                    continue;
+                };
+                let Some(name) = stem.strip_prefix("day") else {
+                    continue;
+                };
+                let (day, suffix) = name.split_once('_').unwrap_or((name, ""));
+                if let Ok(day) = day.parse::<u8>() {
+                    let path = fs::canonicalize(&path).unwrap();
+                    examples.push((year, day, suffix.to_string(), path));
+                }
+            }
+        }
+    }
+    examples.sort();
+
+    let entries = examples
+        .iter()
+        .map(|(year, day, suffix, path)| {
+            format!(
+                "    ({}, {}, {:?}, include_str!({:?})),\n",
+                year, day, suffix, path
+            )
+        })
+        .collect::<String>();
+    fs::write(
+        out,
+        format!(
+            "/// Example inputs as `(year, day, suffix, input)`, sorted by year, day and suffix.\n\
+             pub static EXAMPLES: &[(u16, u8, &str, &str)] = &[\n{}];\n",
+            entries
+        ),
+    )
+    .unwrap();
+}
+
+fn write_answers(out: &Path) {
+    let mut answers = vec![];
+    if env::var_os("CARGO_FEATURE_TUI").is_some() {
+        for year_dir in fs::read_dir("src").into_iter().flatten().flatten() {
+            let Some(year) = year_dir
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix('y'))
+                .and_then(|y| y.parse::<u16>().ok())
+            else {
+                continue;
+            };
+            println!("cargo:rerun-if-changed={}", year_dir.path().display());
+            for file in fs::read_dir(year_dir.path())
+                .into_iter()
+                .flatten()
+                .flatten()
+            {
+                let path = file.path();
+                let Some(day) = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .and_then(|stem| stem.strip_prefix("day"))
+                    .and_then(|day| day.parse::<u8>().ok())
+                else {
+                    continue;
+                };
+                let source = fs::read_to_string(&path).unwrap();
+                for part in 1..=2 {
+                    if let Some(answer) = test_answer(&source, part) {
+                        answers.push((year, day, part, answer.to_string()));
+                    }
+                }
+            }
+        }
+    }
+    answers.sort();
+
+    let entries = answers
+        .iter()
+        .map(|(year, day, part, answer)| {
+            format!("    ({}, {}, {}, {:?}),\n", year, day, part, answer)
+        })
+        .collect::<String>();
+    fs::write(
+        out,
+        format!(
+            "/// Answers of the puzzle inputs as `(year, day, part, answer)`, sorted by year, day and part.\n\
+             static ANSWERS: &[(u16, u8, u8, &str)] = &[\n{}];\n",
+            entries
+        ),
+    )
+    .unwrap();
+}
+
+/// Returns the answer asserted in `test_part<part>_input`, as in
+/// `assert_eq!(solution.part1(), "42");`.
+fn test_answer(source: &str, part: u8) -> Option<&str> {
+    let (_, test) = source.split_once(&format!("fn test_part{}_input()", part))?;
+    let test = &test[..test.find("#[test]").unwrap_or(test.len())];
+    let (_, answer) = test.split_once(&format!("assert_eq!(solution.part{}(), \"", part))?;
+    let (answer, _) = answer.split_once("\");")?;
+    Some(answer)
+}
@@ -0,0 +1,58 @@
+/*
+ * C ABI of the Advent of Code solutions, implemented in `src/aoc/ffi.rs`.
+ *
+ * Build the shared library with:
+ *   cargo rustc --release --lib --no-default-features --features ffi --crate-type cdylib
+ *
+ * All functions can be called from any thread, concurrent calls of `aoc_run` are serialized.
+ *
+ * This header is maintained by hand, `test_header` in `src/aoc/ffi.rs` compares all of it with
+ * the Rust declarations.
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define AOC_PART_1 1
+#define AOC_PART_2 2
+#define AOC_PART_BOTH 3
+
+/* Result of `aoc_run`, either the answers of the requested parts or `error` are set, the other
+ * fields are NULL. */
+typedef struct AocResult {
+    char *answer1;
+    char *answer2;
+    char *error;
+    /* Captured output of the solution, lines separated by '\n' */
+    char *output;
+    /* Time for parsing the input and solving the part(s) */
+    uint64_t time_ns;
+} AocResult;
+
+/* Lists all solutions, one per line as "<year> <day> <title>".
+ * The string must be released with `aoc_free_string`. */
+char *aoc_list(void);
+
+/* Runs a part (see AOC_PART_*) of a solution with `len` bytes of UTF-8 input.
+ * `input` may be NULL if `len` is 0. The result must be released with `aoc_free`.
+ * Blocks while another thread runs a solution. */
+AocResult *aoc_run(uint16_t year, uint8_t day, uint8_t part, const uint8_t *input, size_t len);
+
+/* Releases a result returned by `aoc_run`, NULL is ignored. */
+void aoc_free(AocResult *result);
+
+/* Releases a string returned by `aoc_list`, NULL is ignored. */
+void aoc_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* AOC_H */
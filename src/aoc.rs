@@ -1,6 +1,7 @@
 pub mod day;
 pub mod input;
 pub mod output;
+pub mod params;
 pub mod part;
 
 #[cfg(feature = "online")]
@@ -8,5 +9,8 @@ mod online;
 
 pub mod cli;
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 #[cfg(feature = "wasm")]
 pub mod wasm;
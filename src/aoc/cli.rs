@@ -2,3 +2,7 @@ pub mod args;
 pub mod benchmark;
 pub mod print_results;
 pub mod run;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "tui")]
+pub mod tui;
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
 use itertools::join;
 
@@ -7,8 +9,10 @@ use crate::{aoc::part::Part, Solutions};
 #[command(author, version, about = "Advent of Code soltions in rust.", long_about = None)]
 #[command(override_usage = "
 \taoc list
-\taoc run [-y <year>] [-d <day>] [-p <part>] [-i <input>]
-\taoc bench [-y <year>] [-d <day>] [-p <part>] [-i <input>]
+\taoc run [-y <year>] [-d <day>] [-p <part>] [-i <input>] [--param <name=value>] [--viz <dir>] [--animate [--fps <fps>]]
+\taoc bench [-y <year>] [-d <day>] [-p <part>] [-i <input>] [--param <name=value>]
+\taoc tui
+\taoc serve [--host <host>] [--port <port>]
 ")]
 pub struct Cli {
     /// When to produce colored output
@@ -29,6 +33,14 @@ pub enum Commands {
 
     /// Run a solution and print some benchmark times
     Bench(RunArgs),
+
+    /// Browse and run solutions in an interactive terminal UI
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Serve solutions as a JSON API over HTTP
+    #[cfg(feature = "serve")]
+    Serve(ServeArgs),
 }
 
 #[derive(Args)]
@@ -50,10 +62,28 @@ pub struct RunArgs {
     #[arg(short, long, group = "input-args", requires = "day")]
     pub input: Option<String>,
 
+    /// Override a parameter of the solution, like `--param size=11x7` (see `aoc list`)
+    #[arg(long = "param", value_name = "NAME=VALUE", requires = "day")]
+    pub params: Vec<String>,
+
     /// Download and cache input file
     #[cfg(feature = "online")]
     #[arg(long, group = "input-args")]
     pub download: bool,
+
+    /// Play animations of simulations in the terminal
+    #[cfg(feature = "animate")]
+    #[arg(long)]
+    pub animate: bool,
+
+    /// Frames per second for animations
+    #[cfg(feature = "animate")]
+    #[arg(long, default_value_t = 10, requires = "animate", value_parser = clap::value_parser!(u32).range(1..=1000))]
+    pub fps: u32,
+
+    /// Write visualisations (PNG, SVG, GIF) to `<DIR>/<year>/day<day>/`
+    #[arg(long, value_name = "DIR")]
+    pub viz: Option<PathBuf>,
 }
 
 impl RunArgs {
@@ -70,6 +100,18 @@ impl RunArgs {
     }
 }
 
+#[cfg(feature = "serve")]
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Port to listen on
+    #[arg(long, default_value_t = 8080)]
+    pub port: u16,
+}
+
 #[derive(ValueEnum, Clone)]
 pub enum ColorMode {
     /// Automatically detect if the output is a terminal and use colors if so.
@@ -73,9 +73,11 @@ fn collect_times(day: Day, input_path: &Path, part: &Part) -> Result<Times, Stri
     let loops = env::var(AOC_BENCH_LOOPS_ENV_VAR)
         .map(|v| v.parse::<u16>().unwrap_or(AOC_BENCH_LOOPS))
         .unwrap_or(AOC_BENCH_LOOPS);
+    // the input is only read once, so parsing times don't include file IO
+    let input = input::read_input(input_path)?;
     for _i in 0..loops {
         let start = std::time::Instant::now();
-        let solution = day.parse(&mut input::read_input(input_path)?);
+        let solution = day.parse(&input);
         times.parsing.push(start.elapsed());
 
         if part == &Part::Part1 || part == &Part::Both {
@@ -1,4 +1,4 @@
-use std::{io::BufRead, path::Path};
+use std::path::Path;
 
 use colored::Colorize;
 
@@ -14,12 +14,12 @@ pub struct PrintResults {}
 
 impl Run for PrintResults {
     fn run(day: Day, part: &Part, input_path: &Path) -> Result<(), String> {
-        run_and_print_results(day, part, &mut input::read_input(input_path)?);
+        run_and_print_results(day, part, &input::read_input(input_path)?);
         Ok(())
     }
 }
 
-pub fn run_and_print_results(day: Day, part: &Part, input: &mut dyn BufRead) {
+pub fn run_and_print_results(day: Day, part: &Part, input: &str) {
     output::println(format!(
         "Day {} {}: {}",
         day.day,
@@ -1,4 +1,11 @@
-use std::path::Path;
+use std::{
+    any::Any,
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    sync::Once,
+    time::{Duration, Instant},
+};
 
 use crate::aoc::{day::Day, part::Part};
 
@@ -7,3 +14,63 @@ pub type RunFunction = fn(day: Day, part: &Part, input_path: &Path) -> Result<()
 pub trait Run {
     fn run(day: Day, part: &Part, input_path: &Path) -> Result<(), String>;
 }
+
+thread_local! {
+    static INSIDE_TIMED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` and measures the time, a panic is returned as error with the panic message.
+pub fn timed<T>(f: impl FnOnce() -> T) -> Result<(T, Duration), String> {
+    // the default hook would print the panic into the terminal UI or the server log, it's only
+    // silenced for panics on the current thread so other threads can still swap or use it
+    static SILENCE_HOOK: Once = Once::new();
+    SILENCE_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !INSIDE_TIMED.with(Cell::get) {
+                hook(info);
+            }
+        }));
+    });
+
+    let outer = INSIDE_TIMED.with(|inside| inside.replace(true));
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let time = start.elapsed();
+    INSIDE_TIMED.with(|inside| inside.set(outer));
+
+    result
+        .map(|value| (value, time))
+        .map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Returns the message of a panic payload, which is either a `&str` or a `String`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timed_panic() {
+        assert_eq!(timed(|| 42).map(|(value, _)| value), Ok(42));
+        let result = timed(|| -> u8 { panic!("Invalid input: {}", 7) });
+        assert_eq!(result.err(), Some("Invalid input: 7".to_string()));
+    }
+
+    #[test]
+    fn test_timed_nested() {
+        let result = timed(|| timed(|| -> u8 { panic!("inner") }).err());
+        assert_eq!(
+            result.map(|(inner, _)| inner),
+            Ok(Some("inner".to_string()))
+        );
+        assert!(!INSIDE_TIMED.with(Cell::get));
+    }
+}
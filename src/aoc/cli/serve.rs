@@ -0,0 +1,445 @@
+//! Local HTTP service (`aoc serve`) to run solutions from other tools.
+//!
+//! * `GET /solutions` lists all years and days with their titles and parameters.
+//! * `POST /run/<year>/<day>?part=<1|2|both>` runs a solution with the request body as input.
+//!   Parameters can be overridden like on the command line with `param=<name>=<value>`.
+//!
+//! Responses are JSON with the answers, the timings in nanoseconds and the captured output.
+//! Requests are handled one after another, as the output capturing is global.
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use crate::{
+    aoc::{output, part::Part},
+    common::animation,
+    Solutions,
+};
+
+use super::run::timed;
+
+/// Maximum size of a request body, the largest inputs are around 100 KiB.
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, error: &str) -> Self {
+        Self {
+            status,
+            body: format!("{{\"error\":{}}}", json_string(error)),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Listens on `host:port` and serves requests until the process is stopped.
+pub fn run(host: &str, port: u16) -> Result<(), String> {
+    animation::disable();
+    // colors would be captured as escape codes
+    colored::control::set_override(false);
+
+    let listener = TcpListener::bind((host, port))
+        .map_err(|error| format!("Can't listen on {}:{}: {}", host, port, error))?;
+    println!("Listening on http://{}:{}", host, port);
+    serve(listener);
+    Ok(())
+}
+
+fn serve(listener: TcpListener) {
+    for stream in listener.incoming() {
+        let result = stream.and_then(handle_connection);
+        if let Err(error) = result {
+            eprintln!("Connection error: {}", error);
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) -> io::Result<()> {
+    // a stalled client would block all other requests
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => {
+            let response = handle(&request);
+            println!("{} {} {}", request.method, request.path, response.status);
+            response
+        }
+        Err(response) => response,
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, Response> {
+    let bad_request = |error: &str| Response::error(400, error);
+
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .map_err(|_| bad_request("Invalid request"))?;
+    let mut request_line = line.split_whitespace();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(bad_request("Invalid request line"));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let method = method.to_string();
+    let path = path.to_string();
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect();
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        reader
+            .read_line(&mut line)
+            .map_err(|_| bad_request("Invalid headers"))?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| bad_request("Invalid Content-Length"))?;
+            }
+        }
+    }
+    if content_length > MAX_BODY_SIZE {
+        return Err(Response::error(413, "Input is too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| bad_request("Incomplete body"))?;
+
+    Ok(Request {
+        method,
+        path,
+        query,
+        body: String::from_utf8(body).map_err(|_| bad_request("Input must be UTF-8"))?,
+    })
+}
+
+fn handle(request: &Request) -> Response {
+    let segments = request
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["solutions"]) => Response::ok(solutions_json()),
+        ("POST", ["run", year, day]) => run_solution(year, day, request),
+        (_, ["solutions"] | ["run", _, _]) => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+fn solutions_json() -> String {
+    let years = Solutions::years().into_iter().map(|year| {
+        let days = Solutions::days_for_year(year).into_iter().map(|day| {
+            let solution = Solutions::get(year, day);
+            let params = solution.params.iter().map(|param| {
+                format!(
+                    "{{\"name\":{},\"default\":{},\"example\":{},\"description\":{}}}",
+                    json_string(param.name),
+                    json_string(param.default),
+                    param.example.map_or("null".to_string(), json_string),
+                    json_string(param.description)
+                )
+            });
+            format!(
+                "{{\"day\":{},\"title\":{},\"params\":{}}}",
+                day,
+                json_string(solution.title),
+                json_array(params)
+            )
+        });
+        format!("{{\"year\":{},\"days\":{}}}", year, json_array(days))
+    });
+    format!("{{\"years\":{}}}", json_array(years))
+}
+
+fn run_solution(year: &str, day: &str, request: &Request) -> Response {
+    let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) else {
+        return Response::error(404, "Not found");
+    };
+    if !Solutions::days_for_year(year).contains(&day) {
+        return Response::error(404, &format!("No solution for day {} {}", day, year));
+    }
+
+    let mut part = Part::Both;
+    let mut params = vec![];
+    for (name, value) in &request.query {
+        match name.as_str() {
+            "part" => match value.parse() {
+                Ok(value) => part = value,
+                Err(error) => return Response::error(400, error),
+            },
+            "param" => params.push(value.clone()),
+            _ => return Response::error(400, &format!("Unknown query parameter: {}", name)),
+        }
+    }
+    let solution = match Solutions::get(year, day).with_params(&params) {
+        Ok(solution) => solution,
+        Err(error) => return Response::error(400, &error),
+    };
+    let parts = match part {
+        Part::Both => vec![Part::Part1, Part::Part2],
+        part => vec![part],
+    };
+
+    output::capture();
+    let (parse, parts) = match timed(|| solution.parse(&request.body)) {
+        Ok((parsed, time)) => {
+            let parts = parts.into_iter().map(|part| {
+                let result = match timed(|| part.run_for(parsed.as_ref())) {
+                    Ok((answer, time)) => format!(
+                        "\"answer\":{},\"time_ns\":{}",
+                        json_string(&answer),
+                        time.as_nanos()
+                    ),
+                    Err(error) => format!("\"error\":{}", json_string(&error)),
+                };
+                format!("{{\"part\":\"{}\",{}}}", part, result)
+            });
+            let parts = json_array(parts);
+            (format!("{{\"time_ns\":{}}}", time.as_nanos()), parts)
+        }
+        Err(error) => (
+            format!("{{\"error\":{}}}", json_string(&error)),
+            "[]".to_string(),
+        ),
+    };
+    let output = output::take_captured();
+
+    Response::ok(format!(
+        "{{\"year\":{},\"day\":{},\"title\":{},\"parse\":{},\"parts\":{},\"output\":{}}}",
+        year,
+        day,
+        json_string(solution.title),
+        parse,
+        parts,
+        json_array(output.iter().map(|line| json_string(line)))
+    ))
+}
+
+/// Decodes a query component, `+` is a space and `%XX` a byte. Invalid escapes are kept as they
+/// are.
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn json_array(values: impl Iterator<Item = String>) -> String {
+    format!("[{}]", values.collect::<Vec<_>>().join(","))
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Read, net::Shutdown, thread};
+
+    use super::*;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    fn request(method: &str, target: &str, body: &str) -> Request {
+        let raw = format!(
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            target,
+            body.len(),
+            body
+        );
+        read_request(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_read_request() {
+        assert_eq!(
+            request("POST", "/run/2024/1?part=2&param=size=11x7", "1 2\n"),
+            Request {
+                method: "POST".to_string(),
+                path: "/run/2024/1".to_string(),
+                query: vec![
+                    ("part".to_string(), "2".to_string()),
+                    ("param".to_string(), "size=11x7".to_string())
+                ],
+                body: "1 2\n".to_string(),
+            }
+        );
+        assert_eq!(
+            request("POST", "/run/2024/14?part=1&param=size%3D11x7", "")
+                .query
+                .last(),
+            Some(&("param".to_string(), "size=11x7".to_string()))
+        );
+        assert_eq!(
+            read_request(&mut "GET\r\n\r\n".as_bytes())
+                .unwrap_err()
+                .status,
+            400
+        );
+    }
+
+    #[test]
+    fn test_solutions() {
+        let response = handle(&request("GET", "/solutions", ""));
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .contains("{\"day\":1,\"title\":\"Historian Hysteria\",\"params\":[]}"));
+        assert!(response
+            .body
+            .contains("{\"name\":\"size\",\"default\":\"101x103\",\"example\":\"11x7\","));
+    }
+
+    #[test]
+    fn test_run() {
+        let response = handle(&request("POST", "/run/2024/1", EXAMPLE));
+        assert_eq!(response.status, 200);
+        assert!(response.body.starts_with(
+            "{\"year\":2024,\"day\":1,\"title\":\"Historian Hysteria\",\"parse\":{\"time_ns\":"
+        ));
+        assert!(response.body.contains("{\"part\":\"1\",\"answer\":\"11\","));
+        assert!(response.body.contains("{\"part\":\"2\",\"answer\":\"31\","));
+
+        let response = handle(&request("POST", "/run/2024/1?part=2", EXAMPLE));
+        assert!(!response.body.contains("\"part\":\"1\""));
+        assert!(response.body.contains("\"answer\":\"31\""));
+    }
+
+    #[test]
+    fn test_errors() {
+        let status = |method, target| handle(&request(method, target, EXAMPLE)).status;
+        assert_eq!(status("POST", "/run/2024/1?part=3"), 400);
+        assert_eq!(status("POST", "/run/2024/1?foo=bar"), 400);
+        assert_eq!(status("POST", "/run/2024/26"), 404);
+        assert_eq!(status("POST", "/run/2024/x"), 404);
+        assert_eq!(status("GET", "/run/2024/1"), 405);
+        assert_eq!(status("GET", "/"), 404);
+
+        let response = handle(&request("POST", "/run/2024/1", "invalid"));
+        assert_eq!(response.status, 200);
+        assert!(response.body.contains("\"parse\":{\"error\":"));
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("size%3D11x7"), "size=11x7");
+        assert_eq!(percent_decode("a+b%20c"), "a b c");
+        assert_eq!(percent_decode("%E2%94%82"), "│");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%2"), "%zz%2");
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("a \"b\"\n\\"), "\"a \\\"b\\\"\\n\\\\\"");
+        assert_eq!(json_string("│ \u{1b}"), "\"│ \\u001b\"");
+    }
+
+    #[test]
+    fn test_connection() {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = thread::spawn(move || handle_connection(listener.accept().unwrap().0));
+
+        let mut client = TcpStream::connect(address).unwrap();
+        write!(client, "GET /solutions HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        client.shutdown(Shutdown::Write).unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\r\nContent-Type: application/json\r\n"));
+        assert!(response.ends_with("]}]}"));
+    }
+}
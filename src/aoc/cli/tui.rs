@@ -0,0 +1,527 @@
+//! Interactive terminal UI (`aoc tui`) with a calendar of all years and days.
+//!
+//! Days are colored by their state: not implemented (gray), implemented (white), answered but
+//! without expected answers (yellow), verified against the answers asserted in the
+//! `test_part<part>_input` tests (green) and wrong or failed (red). The selected day can be
+//! run with the puzzle input or one of its example inputs, the answers, timings and the captured
+//! output of the last run are shown below the calendar.
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, PrintStyledContent, Stylize},
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::{
+    aoc::{input, output, params::example_args, part::Part},
+    common::animation,
+    Solutions,
+};
+
+use super::run::timed;
+
+const HELP: &str = "←↑↓→ select │ enter run │ 1/2 run part │ e next example │ i puzzle input │ pgup/pgdn scroll │ q quit";
+
+/// Lines above the output of the selected day, without the calendar.
+const DETAIL_LINES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputSource {
+    Puzzle,
+    /// Index into the example inputs of the day
+    Example(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DayState {
+    Missing,
+    Implemented,
+    Answered,
+    Verified,
+    Wrong,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    Run(Part),
+    Quit,
+}
+
+struct PartResult {
+    part: Part,
+    answer: Result<String, String>,
+    time: Duration,
+}
+
+struct RunResult {
+    source: InputSource,
+    input_path: PathBuf,
+    parse: Result<Duration, String>,
+    parts: Vec<PartResult>,
+    output: Vec<String>,
+}
+
+struct App {
+    years: Vec<u16>,
+    year: usize,
+    day: u8,
+    source: InputSource,
+    results: HashMap<(u16, u8), RunResult>,
+    /// First line of the output which is shown
+    scroll: usize,
+    message: Option<String>,
+}
+
+/// Runs the terminal UI until it's closed with `q`.
+pub fn run() -> Result<(), String> {
+    animation::disable();
+    // colors would be captured as escape codes
+    colored::control::set_override(false);
+
+    let mut app = App::new(Solutions::years());
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode().map_err(|error| format!("Terminal error: {}", error))?;
+    let result = execute!(stdout, EnterAlternateScreen, cursor::Hide)
+        .and_then(|_| app.event_loop(&mut stdout));
+    let _ = execute!(stdout, cursor::Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+    result.map_err(|error| format!("Terminal error: {}", error))
+}
+
+impl App {
+    fn new(years: Vec<u16>) -> Self {
+        let year = years.len().saturating_sub(1);
+        let day = years
+            .get(year)
+            .and_then(|&year| Solutions::days_for_year(year).first().copied())
+            .unwrap_or(1);
+        Self {
+            years,
+            year,
+            day,
+            source: InputSource::Puzzle,
+            results: HashMap::new(),
+            scroll: 0,
+            message: None,
+        }
+    }
+
+    fn selected(&self) -> (u16, u8) {
+        (self.years[self.year], self.day)
+    }
+
+    fn is_implemented(&self, year: u16, day: u8) -> bool {
+        Solutions::days_for_year(year).contains(&day)
+    }
+
+    fn event_loop(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+        loop {
+            self.draw(stdout)?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match self.handle_key(key) {
+                Action::None => {}
+                Action::Run(part) => {
+                    self.message = Some("Running...".to_string());
+                    self.draw(stdout)?;
+                    self.run_selected(part);
+                }
+                Action::Quit => return Ok(()),
+            }
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Action {
+        self.message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Action::Quit
+            }
+            KeyCode::Left => self.select(self.year, self.day.saturating_sub(1).max(1)),
+            KeyCode::Right => self.select(self.year, (self.day + 1).min(25)),
+            KeyCode::Up => self.select(self.year.saturating_sub(1), self.day),
+            KeyCode::Down => self.select((self.year + 1).min(self.years.len() - 1), self.day),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll += 10,
+            KeyCode::Char('i') => self.source = InputSource::Puzzle,
+            KeyCode::Char('e') => {
+                let (year, day) = self.selected();
+                let examples = input::get_example_paths(year, day).len();
+                self.source = match self.source {
+                    _ if examples == 0 => {
+                        self.message = Some("No example inputs for this day".to_string());
+                        InputSource::Puzzle
+                    }
+                    InputSource::Example(i) if i + 1 < examples => InputSource::Example(i + 1),
+                    _ => InputSource::Example(0),
+                };
+            }
+            KeyCode::Enter | KeyCode::Char('r') => return self.run_action(Part::Both),
+            KeyCode::Char('1') => return self.run_action(Part::Part1),
+            KeyCode::Char('2') => return self.run_action(Part::Part2),
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn select(&mut self, year: usize, day: u8) {
+        if (year, day) != (self.year, self.day) {
+            self.year = year;
+            self.day = day;
+            self.source = InputSource::Puzzle;
+            self.scroll = 0;
+        }
+    }
+
+    fn run_action(&mut self, part: Part) -> Action {
+        let (year, day) = self.selected();
+        if self.is_implemented(year, day) {
+            Action::Run(part)
+        } else {
+            self.message = Some(format!("Day {} {} is not implemented", day, year));
+            Action::None
+        }
+    }
+
+    fn input_path(&self) -> Result<PathBuf, String> {
+        let (year, day) = self.selected();
+        match self.source {
+            InputSource::Puzzle => input::get_default_input_path(year, day, false),
+            InputSource::Example(i) => input::get_example_paths(year, day)
+                .into_iter()
+                .nth(i)
+                .ok_or_else(|| "Example input doesn't exist".to_string()),
+        }
+    }
+
+    /// Runs the selected day, panics of the solution are shown as errors.
+    fn run_selected(&mut self, part: Part) {
+        self.message = None;
+        let (year, day) = self.selected();
+        let read = self
+            .input_path()
+            .and_then(|path| Ok((input::read_input(&path)?, path)));
+        let (input, input_path) = match read {
+            Ok(read) => read,
+            Err(error) => {
+                self.message = Some(error);
+                return;
+            }
+        };
+        let parts = match part {
+            Part::Both => vec![Part::Part1, Part::Part2],
+            part => vec![part],
+        };
+
+        // the example inputs need other parameters, like a smaller size
+        let solution = match self.source {
+            InputSource::Puzzle => Ok(Solutions::get(year, day)),
+            InputSource::Example(_) => {
+                let solution = Solutions::get(year, day);
+                let args = example_args(solution.params);
+                solution.with_params(&args)
+            }
+        };
+        let solution = match solution {
+            Ok(solution) => solution,
+            Err(error) => {
+                self.message = Some(error);
+                return;
+            }
+        };
+
+        output::capture();
+        let parsed = timed(|| solution.parse(&input));
+        let (parse, parts) = match parsed {
+            Ok((solution, time)) => {
+                let parts = parts
+                    .into_iter()
+                    .map(|part| {
+                        let result = timed(|| part.run_for(solution.as_ref()));
+                        PartResult {
+                            part,
+                            time: result.as_ref().map_or(Duration::ZERO, |(_, time)| *time),
+                            answer: result.map(|(answer, _)| answer),
+                        }
+                    })
+                    .collect();
+                (Ok(time), parts)
+            }
+            Err(error) => (Err(error), vec![]),
+        };
+
+        let result = RunResult {
+            source: self.source,
+            input_path,
+            parse,
+            parts,
+            output: output::take_captured(),
+        };
+        self.scroll = 0;
+        self.results.insert((year, day), result);
+    }
+
+    fn state(&self, year: u16, day: u8) -> DayState {
+        if !self.is_implemented(year, day) {
+            return DayState::Missing;
+        }
+        let Some(result) = self.results.get(&(year, day)) else {
+            return DayState::Implemented;
+        };
+        if result.parse.is_err() || result.parts.iter().any(|part| part.answer.is_err()) {
+            return DayState::Wrong;
+        }
+        if result.source != InputSource::Puzzle {
+            return DayState::Implemented;
+        }
+        let checks = result
+            .parts
+            .iter()
+            .map(|part| {
+                expected_answer(year, day, part.part)
+                    .map(|expected| part.answer.as_deref() == Ok(expected))
+            })
+            .collect::<Vec<_>>();
+        if checks.contains(&Some(false)) {
+            DayState::Wrong
+        } else if checks.contains(&None) {
+            DayState::Answered
+        } else {
+            DayState::Verified
+        }
+    }
+
+    fn draw(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let (width, height) = (width as usize, height as usize);
+        let plain = |text: String| truncate(&text, width).stylize();
+        let mut lines = vec![];
+
+        let header = (1..=25)
+            .map(|day| format!("{:>3}", day))
+            .collect::<String>();
+        lines.push(vec![plain(format!("      {}", header))]);
+        for (index, &year) in self.years.iter().enumerate() {
+            let mut line = vec![plain(format!("{:>5} ", year))];
+            for day in 1..=25 {
+                let color = match self.state(year, day) {
+                    DayState::Missing => Color::DarkGrey,
+                    DayState::Implemented => Color::White,
+                    DayState::Answered => Color::Yellow,
+                    DayState::Verified => Color::Green,
+                    DayState::Wrong => Color::Red,
+                };
+                let cell = format!("{:>3}", day).with(color);
+                if (index, day) == (self.year, self.day) {
+                    line.push(cell.reverse());
+                } else {
+                    line.push(cell);
+                }
+            }
+            lines.push(line);
+        }
+        lines.push(vec![]);
+
+        let output_height = height.saturating_sub(lines.len() + DETAIL_LINES + 1);
+        lines.extend(self.details().into_iter().map(|line| vec![plain(line)]));
+        if let Some(result) = self.results.get(&self.selected()) {
+            let title = format!("── Output ({} lines) ──", result.output.len());
+            lines.push(vec![plain(title)]);
+            let scroll = self.scroll.min(result.output.len().saturating_sub(1));
+            for line in result.output.iter().skip(scroll).take(output_height) {
+                lines.push(vec![plain(line.clone())]);
+            }
+        }
+
+        queue!(stdout, Clear(ClearType::All))?;
+        for (y, line) in lines.into_iter().take(height.saturating_sub(1)).enumerate() {
+            queue!(stdout, cursor::MoveTo(0, y as u16))?;
+            for content in line {
+                queue!(stdout, PrintStyledContent(content))?;
+            }
+        }
+        let footer = self.message.as_deref().unwrap_or(HELP);
+        queue!(
+            stdout,
+            cursor::MoveTo(0, height.saturating_sub(1) as u16),
+            PrintStyledContent(truncate(footer, width).dark_grey())
+        )?;
+        stdout.flush()
+    }
+
+    /// Lines with the title, input and answers of the selected day.
+    fn details(&self) -> Vec<String> {
+        let (year, day) = self.selected();
+        if !self.is_implemented(year, day) {
+            return vec![format!("Day {} {} is not implemented", day, year)];
+        }
+
+        let solution = Solutions::get(year, day);
+        let mut lines = vec![format!("Day {} {}: {}", day, year, solution.title)];
+        lines.push(match self.input_path() {
+            Ok(path) => format!("Input: {}", path.display()),
+            Err(error) => format!("Input: {}", error),
+        });
+
+        let Some(result) = self.results.get(&(year, day)) else {
+            return lines;
+        };
+        lines.push(format!("Last run with {}", result.input_path.display()));
+        match &result.parse {
+            Ok(time) => lines.push(format!("├─ Parsing: {:.2?}", time)),
+            Err(error) => lines.push(format!("└─ Parsing failed: {}", error)),
+        }
+        for (i, part) in result.parts.iter().enumerate() {
+            let branch = if i + 1 == result.parts.len() {
+                '└'
+            } else {
+                '├'
+            };
+            let line = match &part.answer {
+                Ok(answer) => {
+                    let expected = expected_answer(year, day, part.part)
+                        .filter(|_| result.source == InputSource::Puzzle);
+                    let check = match expected {
+                        Some(expected) if expected == answer => " ✓".to_string(),
+                        Some(expected) => format!(" ✗ (expected {})", expected),
+                        None => String::new(),
+                    };
+                    format!(
+                        "{}─ Part {}: {} ({:.2?}){}",
+                        branch, part.part, answer, part.time, check
+                    )
+                }
+                Err(error) => format!("{}─ Part {} failed: {}", branch, part.part, error),
+            };
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+include!(concat!(env!("OUT_DIR"), "/answers.rs"));
+
+/// Returns the answer of the puzzle input, embedded by `build.rs`.
+fn expected_answer(year: u16, day: u8, part: Part) -> Option<&'static str> {
+    let part = if part == Part::Part1 { 1 } else { 2 };
+    ANSWERS
+        .iter()
+        .find(|answer| (answer.0, answer.1, answer.2) == (year, day, part))
+        .map(|answer| answer.3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn app_at(year: u16, day: u8) -> App {
+        let years = Solutions::years();
+        let mut app = App::new(years.clone());
+        app.year = years.iter().position(|&y| y == year).unwrap();
+        app.day = day;
+        app
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut app = app_at(2023, 1);
+        app.handle_key(key(KeyCode::Left));
+        assert_eq!(app.selected(), (2023, 1));
+        app.handle_key(key(KeyCode::Right));
+        app.handle_key(key(KeyCode::Up));
+        assert_eq!(app.selected(), (2022, 2));
+        for _ in 0..30 {
+            app.handle_key(key(KeyCode::Right));
+        }
+        assert_eq!(app.day, 25);
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Action::Quit);
+    }
+
+    #[test]
+    fn test_run_actions() {
+        let mut app = app_at(2023, 10);
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Action::None);
+        assert!(app.message.is_some());
+
+        let mut app = app_at(2023, 1);
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('2'))),
+            Action::Run(Part::Part2)
+        );
+        // 2023 day 1 has two examples, `e` cycles through them
+        app.handle_key(key(KeyCode::Char('e')));
+        app.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(app.source, InputSource::Example(1));
+        app.handle_key(key(KeyCode::Char('e')));
+        assert_eq!(app.source, InputSource::Example(0));
+        app.handle_key(key(KeyCode::Right));
+        assert_eq!(app.source, InputSource::Puzzle);
+    }
+
+    #[test]
+    fn test_run_example() {
+        let mut app = app_at(2023, 1);
+        app.handle_key(key(KeyCode::Char('e')));
+        app.run_selected(Part::Part1);
+        let result = &app.results[&(2023, 1)];
+        assert_eq!(result.parts.len(), 1);
+        assert_eq!(result.parts[0].answer, Ok("142".to_string()));
+        // examples are never verified
+        assert_eq!(app.state(2023, 1), DayState::Implemented);
+        assert_eq!(app.state(2023, 10), DayState::Missing);
+    }
+
+    #[test]
+    fn test_run_example_params() {
+        // the example of 2024 day 14 needs the size 11x7
+        let mut app = app_at(2024, 14);
+        app.handle_key(key(KeyCode::Char('e')));
+        app.run_selected(Part::Both);
+        let result = &app.results[&(2024, 14)];
+        assert_eq!(result.parts[0].answer, Ok("12".to_string()));
+        // the example has no tree, part 2 must fail instead of searching forever
+        assert_eq!(result.parts[1].answer, Err("No tree found".to_string()));
+    }
+
+    #[test]
+    fn test_run_puzzle_input() {
+        let mut app = app_at(2023, 1);
+        app.run_selected(Part::Both);
+        assert_eq!(app.state(2023, 1), DayState::Verified);
+
+        let result = app.results.get_mut(&(2023, 1)).unwrap();
+        result.parts[1].answer = Ok("0".to_string());
+        assert_eq!(app.state(2023, 1), DayState::Wrong);
+    }
+
+    #[test]
+    fn test_expected_answer() {
+        assert_eq!(expected_answer(2023, 11, Part::Part2), Some("685038186836"));
+        assert_eq!(expected_answer(2022, 10, Part::Part2), Some("RUAKHBEK"));
+        // the last day has no second part
+        assert_eq!(expected_answer(2022, 25, Part::Part2), None);
+    }
+}
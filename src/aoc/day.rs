@@ -1,11 +1,17 @@
-use std::io::BufRead;
+use std::io::{self, BufRead};
+
+use super::params::{Param, Params};
+
+pub type ParseFunction = fn(&str, &Params) -> Box<dyn DaySolution>;
 
 pub struct Day {
     pub year: u16,
     pub day: u8,
     pub title: &'static str,
+    pub params: &'static [Param],
 
-    parse: fn(&mut dyn BufRead) -> Box<dyn DaySolution>,
+    param_values: Params,
+    parse: ParseFunction,
 }
 
 impl Day {
@@ -13,23 +19,51 @@ impl Day {
         year: u16,
         day: u8,
         title: &'static str,
-        parse: fn(&mut dyn BufRead) -> Box<dyn DaySolution>,
+        params: &'static [Param],
+        parse: ParseFunction,
     ) -> Self {
         Self {
             year,
             day,
             title,
+            params,
+            param_values: Params::defaults(params),
             parse,
         }
     }
 
-    pub fn parse(&self, input: &mut dyn BufRead) -> Box<dyn DaySolution> {
-        (self.parse)(input)
+    /// Overrides parameters of the solution with arguments in the form `name=value`.
+    pub fn with_params(mut self, args: &[String]) -> Result<Self, String> {
+        self.param_values = Params::parse(self.params, args)?;
+        Ok(self)
+    }
+
+    pub fn parse(&self, input: &str) -> Box<dyn DaySolution> {
+        (self.parse)(input, &self.param_values)
+    }
+
+    /// Reads the whole input from a reader before parsing it, for inputs that aren't in memory.
+    pub fn parse_reader(&self, input: &mut dyn BufRead) -> Result<Box<dyn DaySolution>, String> {
+        let input = io::read_to_string(input).map_err(|error| error.to_string())?;
+        Ok(self.parse(&input))
     }
 }
 
-pub trait DayParser {
-    fn with_input(input: &mut dyn BufRead) -> Self;
+pub trait DayParser: Sized {
+    /// Parameters of the solution, like sizes that differ between the example and the input.
+    const PARAMS: &'static [Param] = &[];
+
+    fn with_input(input: &str) -> Self;
+
+    /// Parses the input with the given parameters, solutions with [`Self::PARAMS`] override this.
+    fn with_params(input: &str, _params: &Params) -> Self {
+        Self::with_input(input)
+    }
+
+    /// Reads the whole input from a reader and parses it, panics if the input can't be read.
+    fn with_reader(input: &mut dyn BufRead) -> Self {
+        Self::with_input(&io::read_to_string(input).unwrap())
+    }
 }
 
 pub trait DaySolution {
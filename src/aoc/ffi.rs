@@ -0,0 +1,365 @@
+//! C ABI to embed the solutions in other languages, see `include/aoc.h` for the declarations.
+//!
+//! Build the shared library with:
+//!
+//! ```text
+//! cargo rustc --release --lib --no-default-features --features ffi --crate-type cdylib
+//! ```
+//!
+//! All returned strings and results are owned by the caller and must be released with
+//! [`aoc_free_string`] and [`aoc_free`]. Panics of solutions are caught and returned as errors,
+//! output of the solutions is captured instead of printed.
+//!
+//! All functions can be called from any thread. The output capture is global, so concurrent calls
+//! of [`aoc_run`] are serialized.
+//!
+//! The header is maintained by hand, `test_header` compares all of it with the declarations here.
+
+use std::{
+    ffi::{c_char, CString},
+    ptr, slice,
+    sync::Mutex,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    aoc::{output, part::Part},
+    common::animation,
+    Solutions,
+};
+
+use super::cli::run::timed;
+
+pub const AOC_PART_1: u8 = 1;
+pub const AOC_PART_2: u8 = 2;
+pub const AOC_PART_BOTH: u8 = 3;
+
+/// Held while a solution runs, because the captured output is shared by all threads
+static RUN_LOCK: Mutex<()> = Mutex::new(());
+
+/// Result of [`aoc_run`], either the answers of the requested parts or `error` are set, the other
+/// fields are null.
+#[repr(C)]
+pub struct AocResult {
+    pub answer1: *mut c_char,
+    pub answer2: *mut c_char,
+    pub error: *mut c_char,
+    /// Captured output of the solution, lines separated by `\n`
+    pub output: *mut c_char,
+    /// Time for parsing the input and solving the part(s)
+    pub time_ns: u64,
+}
+
+/// Lists all solutions, one per line as `<year> <day> <title>`.
+/// The string must be released with [`aoc_free_string`].
+#[no_mangle]
+pub extern "C" fn aoc_list() -> *mut c_char {
+    let list = Solutions::years()
+        .into_iter()
+        .flat_map(|year| {
+            Solutions::days_for_year(year)
+                .into_iter()
+                .map(move |day| format!("{} {} {}", year, day, Solutions::get(year, day).title))
+        })
+        .join("\n");
+    to_c_string(list)
+}
+
+/// Runs a part (see `AOC_PART_*`) of a solution with the given UTF-8 input.
+/// The result must be released with [`aoc_free`]. Blocks while another thread runs a solution.
+///
+/// # Safety
+///
+/// `input` must point to `len` readable bytes, it may be null if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_run(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: *const u8,
+    len: usize,
+) -> *mut AocResult {
+    let input = if len == 0 {
+        &[]
+    } else {
+        // SAFETY: the caller guarantees that `input` points to `len` bytes
+        unsafe { slice::from_raw_parts(input, len) }
+    };
+    // panics are caught inside, but a poisoned lock must not make the library unusable
+    let guard = RUN_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    output::capture();
+    let result = run(year, day, part, input);
+    let output = to_c_string(output::take_captured().join("\n"));
+    drop(guard);
+    let result = match result {
+        Ok(([answer1, answer2], time_ns)) => AocResult {
+            answer1: answer1.map_or(ptr::null_mut(), to_c_string),
+            answer2: answer2.map_or(ptr::null_mut(), to_c_string),
+            error: ptr::null_mut(),
+            output,
+            time_ns,
+        },
+        Err(error) => AocResult {
+            answer1: ptr::null_mut(),
+            answer2: ptr::null_mut(),
+            error: to_c_string(error),
+            output,
+            time_ns: 0,
+        },
+    };
+    Box::into_raw(Box::new(result))
+}
+
+/// Releases a result returned by [`aoc_run`], null is ignored.
+///
+/// # Safety
+///
+/// `result` must be returned by [`aoc_run`] and must not be used or released afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free(result: *mut AocResult) {
+    if result.is_null() {
+        return;
+    }
+    // SAFETY: the result was created with `Box::into_raw` in `aoc_run`
+    let result = unsafe { Box::from_raw(result) };
+    unsafe {
+        aoc_free_string(result.answer1);
+        aoc_free_string(result.answer2);
+        aoc_free_string(result.error);
+        aoc_free_string(result.output);
+    }
+}
+
+/// Releases a string returned by [`aoc_list`], null is ignored.
+///
+/// # Safety
+///
+/// `s` must be returned by this library and must not be used or released afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: all strings are created with `CString::into_raw`
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Returns the answers of part 1 and 2, `None` for a part which wasn't requested.
+fn run(year: u16, day: u8, part: u8, input: &[u8]) -> Result<([Option<String>; 2], u64), String> {
+    if !Solutions::days_for_year(year).contains(&day) {
+        return Err(format!("No solution for day {} {}", day, year));
+    }
+    let part = match part {
+        AOC_PART_1 => Part::Part1,
+        AOC_PART_2 => Part::Part2,
+        AOC_PART_BOTH => Part::Both,
+        _ => return Err(format!("Invalid part: {}", part)),
+    };
+    let input = std::str::from_utf8(input).map_err(|_| "Input must be UTF-8".to_string())?;
+
+    animation::disable();
+
+    let solution = Solutions::get(year, day);
+    let (answers, time) = timed(|| {
+        let parsed = solution.parse(input);
+        [Part::Part1, Part::Part2].map(|single| {
+            (part == single || part == Part::Both).then(|| single.run_for(parsed.as_ref()))
+        })
+    })?;
+    Ok((answers, time.as_nanos() as u64))
+}
+
+fn to_c_string(s: String) -> *mut c_char {
+    // answers and errors never contain null bytes, but they shouldn't cut the string either
+    CString::new(s.replace('\0', "\u{fffd}"))
+        .unwrap()
+        .into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::CStr, mem};
+
+    use super::*;
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    /// Runs a solution and returns the answers of part 1 and 2 and the error.
+    unsafe fn run_c(
+        year: u16,
+        day: u8,
+        part: u8,
+        input: &[u8],
+    ) -> (Option<String>, Option<String>, Option<String>) {
+        let to_string = |s: *mut c_char| {
+            (!s.is_null()).then(|| unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned())
+        };
+        unsafe {
+            let result = aoc_run(year, day, part, input.as_ptr(), input.len());
+            let strings = (
+                to_string((*result).answer1),
+                to_string((*result).answer2),
+                to_string((*result).error),
+            );
+            aoc_free(result);
+            strings
+        }
+    }
+
+    fn error(message: &str) -> (Option<String>, Option<String>, Option<String>) {
+        (None, None, Some(message.to_string()))
+    }
+
+    #[test]
+    fn test_list() {
+        let list = aoc_list();
+        let s = unsafe { CStr::from_ptr(list) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { aoc_free_string(list) };
+        assert!(s.lines().any(|line| line == "2024 1 Historian Hysteria"));
+    }
+
+    #[test]
+    fn test_run() {
+        unsafe {
+            assert_eq!(
+                run_c(2024, 1, AOC_PART_1, EXAMPLE.as_bytes()),
+                (Some("11".to_string()), None, None)
+            );
+            assert_eq!(
+                run_c(2024, 1, AOC_PART_2, EXAMPLE.as_bytes()),
+                (None, Some("31".to_string()), None)
+            );
+            assert_eq!(
+                run_c(2024, 1, AOC_PART_BOTH, EXAMPLE.as_bytes()),
+                (Some("11".to_string()), Some("31".to_string()), None)
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_threads() {
+        let threads = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| unsafe { run_c(2024, 1, AOC_PART_2, EXAMPLE.as_bytes()) })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), (None, Some("31".to_string()), None));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            assert_eq!(
+                run_c(2024, 26, AOC_PART_1, b""),
+                error("No solution for day 26 2024")
+            );
+            assert_eq!(run_c(2024, 1, 0, b""), error("Invalid part: 0"));
+            assert_eq!(
+                run_c(2024, 1, AOC_PART_1, b"\xff"),
+                error("Input must be UTF-8")
+            );
+            // panics of the solution are returned as error
+            let (answer1, answer2, error) = run_c(2024, 1, AOC_PART_BOTH, b"invalid");
+            assert_eq!((answer1, answer2), (None, None));
+            assert!(error.is_some());
+
+            let result = aoc_run(2024, 1, AOC_PART_1, ptr::null(), 0);
+            assert!((*result).answer1.is_null() != (*result).error.is_null());
+            assert!((*result).answer2.is_null());
+            assert!(!(*result).output.is_null());
+            aoc_free(result);
+            aoc_free(ptr::null_mut());
+        }
+    }
+
+    /// The whole header without comments and blank lines, the Rust declarations are checked
+    /// against the same types in `test_header`.
+    const HEADER: &[&str] = &[
+        "#ifndef AOC_H",
+        "#define AOC_H",
+        "#include <stddef.h>",
+        "#include <stdint.h>",
+        "#ifdef __cplusplus",
+        "extern \"C\" {",
+        "#endif",
+        "#define AOC_PART_1 1",
+        "#define AOC_PART_2 2",
+        "#define AOC_PART_BOTH 3",
+        "typedef struct AocResult {",
+        "char *answer1;",
+        "char *answer2;",
+        "char *error;",
+        "char *output;",
+        "uint64_t time_ns;",
+        "} AocResult;",
+        "char *aoc_list(void);",
+        "AocResult *aoc_run(uint16_t year, uint8_t day, uint8_t part, const uint8_t *input, size_t len);",
+        "void aoc_free(AocResult *result);",
+        "void aoc_free_string(char *s);",
+        "#ifdef __cplusplus",
+        "}",
+        "#endif",
+        "#endif",
+    ];
+
+    fn strip_comments(mut source: &str) -> String {
+        let mut stripped = String::new();
+        while let Some((code, rest)) = source.split_once("/*") {
+            stripped.push_str(code);
+            source = rest.split_once("*/").map_or("", |(_, rest)| rest);
+        }
+        stripped.push_str(source);
+        stripped
+    }
+
+    #[test]
+    fn test_header() {
+        let header = strip_comments(include_str!("../../include/aoc.h"));
+        let lines = header
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, HEADER);
+
+        // the Rust side of the same declarations, a changed type or signature doesn't compile
+        assert_eq!((AOC_PART_1, AOC_PART_2, AOC_PART_BOTH), (1, 2, 3));
+        let _: extern "C" fn() -> *mut c_char = aoc_list;
+        let _: unsafe extern "C" fn(u16, u8, u8, *const u8, usize) -> *mut AocResult = aoc_run;
+        let _: unsafe extern "C" fn(*mut AocResult) = aoc_free;
+        let _: unsafe extern "C" fn(*mut c_char) = aoc_free_string;
+        // no `..`, so added fields don't compile either
+        fn fields(result: AocResult) -> ([*mut c_char; 4], u64) {
+            let AocResult {
+                answer1,
+                answer2,
+                error,
+                output,
+                time_ns,
+            } = result;
+            ([answer1, answer2, error, output], time_ns)
+        }
+        let _ = fields;
+        let pointer = size_of::<*mut c_char>();
+        assert_eq!(
+            [
+                mem::offset_of!(AocResult, answer1),
+                mem::offset_of!(AocResult, answer2),
+                mem::offset_of!(AocResult, error),
+                mem::offset_of!(AocResult, output),
+                mem::offset_of!(AocResult, time_ns),
+            ],
+            [0, pointer, 2 * pointer, 3 * pointer, 4 * pointer]
+        );
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        assert_eq!(strip_comments("a /* b\n c */d /* e"), "a d ");
+    }
+}
@@ -1,6 +1,5 @@
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+    fs,
     path::{Path, PathBuf},
 };
 
@@ -18,16 +17,40 @@ pub fn get_input_subpath(year: u16, day: u8) -> PathBuf {
     PathBuf::from(format!("input/{}/day{}.txt", year, day))
 }
 
-/// Reads the input file
-pub fn read_input<P: AsRef<Path>>(path: P) -> Result<impl BufRead, String> {
-    let file = File::open(&path).map_err(|error| {
+/// Returns the paths of all example inputs for the given year and day, like
+/// `input/<year>/example/day<day>.txt` and `input/<year>/example/day<day>_2.txt`.
+pub fn get_example_paths(year: u16, day: u8) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(format!("input/{}/example", year)) else {
+        return vec![];
+    };
+    let name = format!("day{}", day);
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| {
+                    stem == name
+                        || stem
+                            .strip_prefix(&name)
+                            .and_then(|suffix| suffix.strip_prefix('_'))
+                            .is_some()
+                })
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+/// Reads the whole input file at once, solutions parse from the returned string.
+pub fn read_input<P: AsRef<Path>>(path: P) -> Result<String, String> {
+    fs::read_to_string(&path).map_err(|error| {
         format!(
-            "Error opening input file ({}): {}",
+            "Error reading input file ({}): {}",
             path.as_ref().display(),
             error
         )
-    })?;
-    Ok(BufReader::new(file))
+    })
 }
 
 /// Returns the path to the input file for the given year and day.
@@ -72,17 +95,41 @@ pub fn get_default_input_path(year: u16, day: u8, download: bool) -> Result<Path
 macro_rules! input {
     (input) => {{
         let (year, day) = $crate::aoc::day::parse_year_and_day_from_module(module_path!());
-        &mut $crate::aoc::input::read_input(&$crate::aoc::input::get_input_subpath(year, day))
+        $crate::aoc::input::read_input(&$crate::aoc::input::get_input_subpath(year, day))
             .unwrap()
+            .as_str()
     }};
     (example) => {{
         let (year, day) = $crate::aoc::day::parse_year_and_day_from_module(module_path!());
         let example_path = format!("input/{}/example/day{}.txt", year, day);
-        &mut $crate::aoc::input::read_input(&std::path::PathBuf::from(example_path)).unwrap()
+        $crate::aoc::input::read_input(&std::path::PathBuf::from(example_path))
+            .unwrap()
+            .as_str()
     }};
     (example, $suffix:literal) => {{
         let (year, day) = $crate::aoc::day::parse_year_and_day_from_module(module_path!());
         let example_path = format!("input/{}/example/day{}_{}.txt", year, day, $suffix);
-        &mut $crate::aoc::input::read_input(&std::path::PathBuf::from(example_path)).unwrap()
+        $crate::aoc::input::read_input(&std::path::PathBuf::from(example_path))
+            .unwrap()
+            .as_str()
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_example_paths() {
+        let paths = get_example_paths(2023, 1);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("input/2023/example/day1.txt"),
+                PathBuf::from("input/2023/example/day1_2.txt"),
+            ]
+        );
+        assert!(get_example_paths(2023, 10).is_empty());
+        assert!(get_example_paths(1999, 1).is_empty());
+    }
+}
@@ -1,10 +1,66 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
 
 static OUTPUT: AtomicBool = AtomicBool::new(true);
 static DEBUG: AtomicBool = AtomicBool::new(true);
+static CAPTURED: Mutex<Option<Vec<String>>> = Mutex::new(None);
+static GRIDS: Mutex<Option<CapturedGrids>> = Mutex::new(None);
+
+/// Maximum number of frames of an animation which are captured, later frames replace the last
+/// one so the final state is always captured.
+pub const MAX_FRAMES: usize = 500;
+
+/// A grid printed with [`println_grid`] or a frame of an animation, with one string per row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Shown next to the grid, like the decoded letters of an OCR image
+    pub title: Option<String>,
+    pub lines: Vec<String>,
+}
+
+/// A captured grid, which has one frame or the frames of an animation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Grid {
+    pub frames: Vec<Frame>,
+    /// Number of frames of a long animation which were dropped before the last frame
+    pub dropped: usize,
+}
+
+#[derive(Default)]
+struct CapturedGrids {
+    grids: Vec<Grid>,
+    /// Whether frames are added to the last grid
+    animating: bool,
+}
+
+impl CapturedGrids {
+    fn push_grid(&mut self, frame: Frame) {
+        self.animating = false;
+        self.grids.push(Grid {
+            frames: vec![frame],
+            dropped: 0,
+        });
+    }
+
+    fn push_frame(&mut self, frame: Frame) {
+        if !self.animating {
+            self.animating = true;
+            self.grids.push(Grid::default());
+        }
+        let grid = self.grids.last_mut().unwrap();
+        if grid.frames.len() < MAX_FRAMES {
+            grid.frames.push(frame);
+        } else {
+            *grid.frames.last_mut().unwrap() = frame;
+            grid.dropped += 1;
+        }
+    }
+}
 
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
@@ -28,23 +84,126 @@ pub fn is_debug_enabled() -> bool {
     DEBUG.load(Ordering::Relaxed)
 }
 
+/// Collects the output in memory instead of printing it, until [`take_captured`] is called.
+pub fn capture() {
+    *CAPTURED.lock().unwrap() = Some(vec![]);
+}
+
+/// Stops capturing and returns the captured lines.
+pub fn take_captured() -> Vec<String> {
+    CAPTURED.lock().unwrap().take().unwrap_or_default()
+}
+
+/// Collects grids and animation frames in memory (for the web app to draw them), until
+/// [`take_grids`] is called. The grids are still printed as debug output.
+pub fn capture_grids() {
+    *GRIDS.lock().unwrap() = Some(CapturedGrids::default());
+}
+
+pub fn is_capturing_grids() -> bool {
+    GRIDS.lock().unwrap().is_some()
+}
+
+/// Stops capturing and returns the captured grids.
+pub fn take_grids() -> Vec<Grid> {
+    GRIDS
+        .lock()
+        .unwrap()
+        .take()
+        .map(|captured| captured.grids)
+        .unwrap_or_default()
+}
+
+/// Prints the lines of a grid as debug output, the title is only used for captured grids.
+pub fn println_grid(title: Option<String>, lines: Vec<String>) {
+    if !is_debug_enabled() {
+        return;
+    }
+    if let Some(captured) = GRIDS.lock().unwrap().as_mut() {
+        captured.push_grid(Frame {
+            title,
+            lines: lines.clone(),
+        });
+    }
+    for line in lines {
+        println_debug(line);
+    }
+}
+
+/// Adds a frame to the current animation if grids are captured, the first frame after
+/// [`finish_frames`] starts a new animation.
+pub fn push_frame(title: Option<String>, lines: Vec<String>) {
+    if let Some(captured) = GRIDS.lock().unwrap().as_mut() {
+        captured.push_frame(Frame { title, lines });
+    }
+}
+
+/// Ends the current animation of the captured grids.
+pub fn finish_frames() {
+    if let Some(captured) = GRIDS.lock().unwrap().as_mut() {
+        captured.animating = false;
+    }
+}
+
 /// Prints a message with newline to stdout if output is enabled
 pub fn println(msg: String) {
     if OUTPUT.load(Ordering::Relaxed) {
-        #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
-        log(&msg);
-
-        #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
-        println!("{}", msg);
+        write_line(msg);
     }
 }
 
 pub fn println_debug(msg: String) {
     if DEBUG.load(Ordering::Relaxed) {
         #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
-        log(&msg);
+        write_line(msg);
 
         #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
-        println!("│  {}", msg);
+        write_line(format!("│  {}", msg));
+    }
+}
+
+fn write_line(msg: String) {
+    if let Some(captured) = CAPTURED.lock().unwrap().as_mut() {
+        captured.push(msg);
+        return;
+    }
+
+    #[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+    log(&msg);
+
+    #[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+    println!("{}", msg);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captured_grids() {
+        let frame = |title: Option<&str>, lines: &str| Frame {
+            title: title.map(String::from),
+            lines: lines.split('/').map(String::from).collect(),
+        };
+
+        let mut captured = CapturedGrids::default();
+        captured.push_grid(frame(Some("A"), "#./.#"));
+        for i in 0..MAX_FRAMES + 2 {
+            captured.push_frame(frame(Some(&i.to_string()), "#"));
+        }
+        captured.push_grid(frame(None, "#"));
+        captured.push_frame(frame(None, ".."));
+
+        let grids = captured.grids;
+        assert_eq!(grids.len(), 4);
+        assert_eq!(grids[0].frames, [frame(Some("A"), "#./.#")]);
+        assert_eq!(grids[1].frames.len(), MAX_FRAMES);
+        assert_eq!(grids[1].frames[1], frame(Some("1"), "#"));
+        // the last frame is kept, the ones before it are dropped
+        let last = (MAX_FRAMES + 1).to_string();
+        assert_eq!(grids[1].frames.last(), Some(&frame(Some(&last), "#")));
+        assert_eq!(grids[1].dropped, 2);
+        assert_eq!(grids[2].dropped, 0);
+        assert_eq!(grids[3].frames, [frame(None, "..")]);
     }
 }
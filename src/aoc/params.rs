@@ -0,0 +1,286 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use glam::IVec2;
+use itertools::Itertools;
+
+/// A named parameter of a solution (like a grid size or number of iterations) with its default value.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    /// Value for the example inputs, if it differs from the default
+    pub example: Option<&'static str>,
+    pub description: &'static str,
+    /// Checks a value before it's used, like [`valid::<u32>`] or [`valid_size`]
+    pub validate: fn(&str) -> Result<(), String>,
+}
+
+/// Accepts values which can be parsed as `T`.
+pub fn valid<T>(value: &str) -> Result<(), String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse::<T>()
+        .map(|_| ())
+        .map_err(|error| error.to_string())
+}
+
+/// Accepts sizes like `11x7` with both components positive, see [`Params::ivec2`].
+pub fn valid_size(value: &str) -> Result<(), String> {
+    let size = parse_ivec2(value)?;
+    if size.cmple(IVec2::ZERO).any() {
+        return Err("size must be positive".to_string());
+    }
+    Ok(())
+}
+
+/// Values of the parameters of a solution, using the default for every parameter that isn't overridden.
+///
+/// ```
+/// # use adventofcode::aoc::params::{example_args, valid, valid_size, Param, Params};
+/// # use glam::IVec2;
+/// const PARAMS: &[Param] = &[
+///     Param { name: "size", default: "101x103", example: Some("11x7"), description: "Size of the area", validate: valid_size },
+///     Param { name: "steps", default: "100", example: None, description: "Number of steps", validate: valid::<u32> },
+/// ];
+/// let params = Params::parse(PARAMS, &["size=11x7".to_string()]).unwrap();
+/// assert_eq!(params.ivec2("size"), IVec2::new(11, 7));
+/// assert_eq!(params.get::<u32>("steps"), 100);
+/// assert!(Params::parse(PARAMS, &["speed=1".to_string()]).is_err());
+/// assert!(Params::parse(PARAMS, &["steps=abc".to_string()]).is_err());
+/// assert_eq!(example_args(PARAMS), vec!["size=11x7"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: HashMap<&'static str, String>,
+}
+
+impl Params {
+    /// Returns the default values of the declared parameters.
+    pub fn defaults(declared: &'static [Param]) -> Self {
+        Self {
+            values: declared
+                .iter()
+                .map(|param| (param.name, param.default.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Returns the declared parameters with some values overridden, the values are validated.
+    pub fn new(declared: &'static [Param], overrides: &[(&str, &str)]) -> Result<Self, String> {
+        let mut params = Self::defaults(declared);
+        for (name, value) in overrides {
+            let param = declared
+                .iter()
+                .find(|param| &param.name == name)
+                .ok_or_else(|| unknown_param(declared, name))?;
+            (param.validate)(value).map_err(|error| {
+                format!(
+                    "Invalid value '{}' for parameter '{}': {}",
+                    value, name, error
+                )
+            })?;
+            params.values.insert(param.name, value.to_string());
+        }
+        Ok(params)
+    }
+
+    /// Returns the declared parameters with overrides in the form `name=value`.
+    pub fn parse(declared: &'static [Param], args: &[String]) -> Result<Self, String> {
+        let overrides = args
+            .iter()
+            .map(|arg| {
+                arg.split_once('=')
+                    .map(|(name, value)| (name.trim(), value.trim()))
+                    .ok_or_else(|| format!("Invalid parameter '{}', expected <name>=<value>", arg))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(declared, &overrides)
+    }
+
+    /// Returns the raw value of a parameter, panics if the parameter wasn't declared.
+    pub fn raw(&self, name: &str) -> &str {
+        self.values
+            .get(name)
+            .unwrap_or_else(|| panic!("Parameter '{}' isn't declared", name))
+    }
+
+    /// Returns the parsed value of a parameter, panics if the value is invalid.
+    pub fn get<T>(&self, name: &str) -> T
+    where
+        T: FromStr,
+        T::Err: Debug,
+    {
+        let value = self.raw(name);
+        value.parse().unwrap_or_else(|error| {
+            panic!(
+                "Invalid value '{}' for parameter '{}': {:?}",
+                value, name, error
+            )
+        })
+    }
+
+    /// Returns a size like `11x7` as vector, panics if the value is invalid.
+    pub fn ivec2(&self, name: &str) -> IVec2 {
+        let value = self.raw(name);
+        parse_ivec2(value).unwrap_or_else(|error| {
+            panic!(
+                "Invalid value '{}' for parameter '{}': {}",
+                value, name, error
+            )
+        })
+    }
+}
+
+/// Returns the overrides in the form `name=value` for running the example inputs.
+pub fn example_args(declared: &[Param]) -> Vec<String> {
+    declared
+        .iter()
+        .filter_map(|param| Some(format!("{}={}", param.name, param.example?)))
+        .collect()
+}
+
+fn unknown_param(declared: &[Param], name: &str) -> String {
+    if declared.is_empty() {
+        format!(
+            "Unknown parameter '{}', this solution has no parameters",
+            name
+        )
+    } else {
+        format!(
+            "Unknown parameter '{}', chose one of: {}",
+            name,
+            declared.iter().map(|param| param.name).join(", ")
+        )
+    }
+}
+
+fn parse_ivec2(value: &str) -> Result<IVec2, String> {
+    let (x, y) = value
+        .split_once('x')
+        .ok_or_else(|| "expected <x>x<y>".to_string())?;
+    let x = x.trim().parse().map_err(|_| format!("invalid x: {}", x))?;
+    let y = y.trim().parse().map_err(|_| format!("invalid y: {}", y))?;
+    Ok(IVec2::new(x, y))
+}
+
+/// Creates the parameters of `Solution` in the current module, for tests.
+///
+/// ```ignore
+/// let solution = Solution::with_params(input!(example), &params!(size = "11x7"));
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($($name:ident = $value:expr),* $(,)?) => {
+        $crate::aoc::params::Params::new(
+            <Solution as $crate::aoc::day::DayParser>::PARAMS,
+            &[$((stringify!($name), $value)),*],
+        )
+        .unwrap()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "size",
+            default: "5x5",
+            example: Some("3x3"),
+            description: "Size",
+            validate: valid_size,
+        },
+        Param {
+            name: "rounds",
+            default: "10",
+            example: None,
+            description: "Rounds",
+            validate: valid::<usize>,
+        },
+    ];
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        let params = Params::defaults(PARAMS);
+        assert_eq!(params.ivec2("size"), IVec2::new(5, 5));
+        assert_eq!(params.get::<usize>("rounds"), 10);
+
+        let params = Params::new(PARAMS, &[("rounds", "3")]).unwrap();
+        assert_eq!(params.get::<usize>("rounds"), 3);
+        assert_eq!(params.raw("size"), "5x5");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Params::parse(PARAMS, &["rounds".to_string()]),
+            Err("Invalid parameter 'rounds', expected <name>=<value>".to_string())
+        );
+        assert_eq!(
+            Params::parse(PARAMS, &["speed=3".to_string()]),
+            Err("Unknown parameter 'speed', chose one of: size, rounds".to_string())
+        );
+        assert_eq!(
+            Params::parse(&[], &["speed=3".to_string()]),
+            Err("Unknown parameter 'speed', this solution has no parameters".to_string())
+        );
+        assert_eq!(
+            Params::parse(PARAMS, &["rounds=abc".to_string()]),
+            Err(
+                "Invalid value 'abc' for parameter 'rounds': invalid digit found in string"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            Params::parse(PARAMS, &["size=5,5".to_string()]),
+            Err("Invalid value '5,5' for parameter 'size': expected <x>x<y>".to_string())
+        );
+        for size in ["0x0", "5x0", "0x7", "-3x5", "5x-1"] {
+            assert_eq!(
+                Params::parse(PARAMS, &[format!("size={}", size)]),
+                Err(format!(
+                    "Invalid value '{}' for parameter 'size': size must be positive",
+                    size
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_ivec2() {
+        assert_eq!(parse_ivec2("11x7"), Ok(IVec2::new(11, 7)));
+        assert_eq!(parse_ivec2(" 3 x -2 "), Ok(IVec2::new(3, -2)));
+        assert!(parse_ivec2("11,7").is_err());
+        assert!(parse_ivec2("ax7").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid value '5x5' for parameter 'size'")]
+    fn test_invalid_value() {
+        Params::defaults(PARAMS).get::<usize>("size");
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        for solution_params in crate::Solutions::years().into_iter().flat_map(|year| {
+            crate::Solutions::days_for_year(year)
+                .into_iter()
+                .map(move |day| crate::Solutions::get(year, day).params)
+        }) {
+            for param in solution_params {
+                assert_eq!((param.validate)(param.default), Ok(()), "{}", param.name);
+                if let Some(example) = param.example {
+                    assert_eq!((param.validate)(example), Ok(()), "{}", param.name);
+                }
+            }
+        }
+    }
+}
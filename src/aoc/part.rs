@@ -4,7 +4,7 @@ use clap::ValueEnum;
 
 use super::day::DaySolution;
 
-#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Part {
     #[value(name = "1")]
     Part1,
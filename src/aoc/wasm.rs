@@ -1,5 +1,9 @@
 pub mod app;
 
+mod canvas;
+mod examples;
+mod input;
 mod run;
-
 mod select;
+mod storage;
+pub mod worker;
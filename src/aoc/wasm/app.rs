@@ -1,14 +1,30 @@
-use web_sys::HtmlTextAreaElement;
+use gloo_timers::callback::Interval;
+use itertools::Itertools;
+use web_sys::HtmlInputElement;
 use yew::{
-    functional::{function_component, use_node_ref, use_state},
+    events::InputEvent,
+    functional::{function_component, use_effect_with, use_mut_ref, use_reducer, use_state},
     html::Html,
     macros::html,
-    ToHtml,
+    Callback, TargetCast, ToHtml,
 };
 
-use crate::{aoc::part::Part, Solutions};
+use crate::{
+    aoc::{params::example_args, part::Part},
+    Solutions,
+};
+
+use super::{
+    canvas::Canvas,
+    examples,
+    input::Input,
+    run::{format_time, Action, Progress, Runner, Status, Step},
+    select::Select,
+    storage,
+    worker::Request,
+};
 
-use super::{run, select::Select};
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
 
 struct Selection {
     year: u16,
@@ -39,63 +55,186 @@ pub fn App() -> Html {
             part: Part::Both,
         }
     });
-    let results = use_state(|| None);
+    let input =
+        use_state(|| storage::load_input(selection.year, selection.day).unwrap_or_default());
+    let progress = use_reducer(Progress::default);
+    let runner = use_mut_ref(|| None::<Runner>);
+    let error = use_state(|| None);
+    let animate = use_state(|| false);
+    let params = use_state(String::new);
+
+    {
+        // updates the elapsed time while running
+        let running = progress.is_running();
+        let progress = progress.dispatcher();
+        use_effect_with(running, move |running| {
+            let interval =
+                running.then(|| Interval::new(100, move || progress.dispatch(Action::Tick)));
+            move || drop(interval)
+        });
+    }
+
+    let clear = {
+        let progress = progress.clone();
+        let runner = runner.clone();
+        Callback::from(move |_| {
+            // the result for the previous selection isn't needed anymore
+            if progress.is_running() {
+                runner.borrow_mut().take();
+            }
+            progress.dispatch(Action::Clear);
+        })
+    };
 
     let on_change_year = {
         let selection = selection.clone();
-        let results = results.clone();
+        let input = input.clone();
+        let params = params.clone();
+        let clear = clear.clone();
         move |year| {
+            let day = Solutions::days_for_year(year).into_iter().min().unwrap();
             selection.set(Selection {
                 year,
-                day: Solutions::days_for_year(year).into_iter().min().unwrap(),
+                day,
                 part: selection.part,
             });
-            results.set(None);
+            input.set(storage::load_input(year, day).unwrap_or_default());
+            params.set(String::new());
+            clear.emit(());
         }
     };
     let on_change_day = {
         let selection = selection.clone();
-        let results = results.clone();
+        let input = input.clone();
+        let params = params.clone();
+        let clear = clear.clone();
         move |day| {
             selection.set(Selection {
                 year: selection.year,
                 day,
                 part: selection.part,
             });
-            results.set(None);
+            input.set(storage::load_input(selection.year, day).unwrap_or_default());
+            params.set(String::new());
+            clear.emit(());
         }
     };
     let on_change_part = {
         let selection = selection.clone();
-        let results = results.clone();
+        let clear = clear.clone();
         move |part| {
             selection.set(Selection {
                 year: selection.year,
                 day: selection.day,
                 part,
             });
-            results.set(None);
+            clear.emit(());
         }
     };
 
-    let input_node_ref = use_node_ref();
+    let on_change_input = {
+        let selection = selection.clone();
+        let input = input.clone();
+        move |value: String| {
+            storage::save_input(selection.year, selection.day, &value);
+            input.set(value);
+        }
+    };
+
+    let day_params = Solutions::get(selection.year, selection.day).params;
+    let example_params = example_args(day_params).join(", ");
+
+    let on_load_example = {
+        let params = params.clone();
+        let example_params = example_params.clone();
+        move |_| params.set(example_params.clone())
+    };
+    let on_input_params = {
+        let params = params.clone();
+        move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            params.set(input.value());
+        }
+    };
 
     let on_run = {
         let selection = selection.clone();
-        let input_node_ref = input_node_ref.clone();
-        let results = results.clone();
+        let input = input.clone();
+        let params = params.clone();
+        let progress = progress.clone();
+        let runner = runner.clone();
+        let clear = clear.clone();
+        let error = error.clone();
+        let animate = animate.clone();
 
         move |_| {
-            let day = Solutions::get(selection.year, selection.day);
-            let input = input_node_ref
-                .cast::<HtmlTextAreaElement>()
-                .unwrap()
-                .value();
+            let params = params
+                .split([',', ' '])
+                .filter(|param| !param.is_empty())
+                .map(|param| param.to_string())
+                .collect_vec();
+            if let Err(e) = Solutions::get(selection.year, selection.day).with_params(&params) {
+                error.set(Some(e));
+                clear.emit(());
+                return;
+            }
+            error.set(None);
+
+            let mut runner = runner.borrow_mut();
+            if !runner.as_ref().is_some_and(Runner::is_usable) {
+                let progress = progress.dispatcher();
+                let on_response =
+                    Callback::from(move |response| progress.dispatch(Action::Response(response)));
+                match Runner::new(on_response) {
+                    Ok(new_runner) => *runner = Some(new_runner),
+                    Err(e) => {
+                        error.set(Some(e));
+                        return;
+                    }
+                }
+            }
+            progress.dispatch(Action::Start {
+                year: selection.year,
+                day: selection.day,
+                part: selection.part,
+            });
+            if let Some(runner) = runner.as_ref() {
+                runner.run(Request {
+                    year: selection.year,
+                    day: selection.day,
+                    part: selection.part,
+                    params,
+                    input: (*input).clone(),
+                    animate: *animate,
+                });
+            }
+        }
+    };
+
+    let on_toggle_animate = {
+        let animate = animate.clone();
+        move |_| animate.set(!*animate)
+    };
 
-            results.set(Some(run::run(day, &selection.part, &mut input.as_bytes())));
+    let on_cancel = {
+        let progress = progress.clone();
+        let runner = runner.clone();
+        move |_| {
+            // terminates the worker, a new one is started for the next run
+            runner.borrow_mut().take();
+            progress.dispatch(Action::Cancel);
         }
     };
 
+    // the example inputs need other parameters, which are set when an example is loaded
+    let examples = examples::examples_for(selection.year, selection.day)
+        .into_iter()
+        .map(|(label, example)| match example_params.as_str() {
+            "" => (label, example),
+            params => (format!("{} ({})", label, params), example),
+        })
+        .collect_vec();
+
     html! {
         <>
             <h1>{ "🎄 Advent of Code - Rust 🦀" }</h1>
@@ -111,28 +250,84 @@ pub fn App() -> Html {
                 { "Selected part: " }
                 <Select<Part> options={Part::values()} selected={Some(selection.part)} onchange={on_change_part} />
             </div>
-            <div>
-                { "Input: " }<br />
-                <textarea ref={input_node_ref} rows="10" cols="50" />
-            </div>
-            <div>
-                <button onclick={on_run}>{ "Run" }</button>
-            </div>
-            if let Some(results) = results.as_ref() {
+            <Input
+                value={(*input).clone()}
+                {examples}
+                onchange={on_change_input}
+                onexample={on_load_example}
+            />
+            if !day_params.is_empty() {
                 <div>
-                    { "Results for " }{ selection.year }{ " day " }{ selection.day }{ ": " }
-                    if let Some(part1) = results.part1.as_ref() {
-                        <div>
-                            { "Part 1: " }{ part1 }
-                        </div>
-                    }
-                    if let Some(part2) = results.part2.as_ref() {
-                        <div>
-                            { "Part 2: " }{ part2 }
-                        </div>
-                    }
+                    { "Parameters: " }
+                    <input
+                        type="text"
+                        value={(*params).clone()}
+                        oninput={on_input_params}
+                        placeholder={day_params.iter().map(|p| format!("{}={}", p.name, p.default)).join(", ")}
+                    />
+                    <ul>
+                        { for day_params.iter().map(|p| html! { <li>{ p.name }{ ": " }{ p.description }</li> }) }
+                    </ul>
                 </div>
             }
+            <div>
+                <label>
+                    <input type="checkbox" checked={*animate} onchange={on_toggle_animate} />
+                    { "Record animations" }
+                </label>
+                <br />
+                <button onclick={on_run} disabled={progress.is_running()}>{ "Run" }</button>
+                if progress.is_running() {
+                    <button onclick={on_cancel}>{ "Cancel" }</button>
+                }
+            </div>
+            if let Some(error) = error.as_ref() {
+                <div>{ "Error: " }{ error }</div>
+            }
+            if !progress.steps.is_empty() {
+                { view_progress(&progress) }
+            }
         </>
     }
 }
+
+fn view_progress(progress: &Progress) -> Html {
+    html! {
+        <div>
+            { "Results for " }{ progress.year }{ " day " }{ progress.day }{ ": " }
+            { for progress.steps.iter().map(|step| view_step(step, progress.now)) }
+            if let Some(error) = progress.error.as_ref() {
+                <div>{ "Error: " }{ error }</div>
+            }
+            { for progress.grids.iter().enumerate().map(|(i, (part, grid))| {
+                let label = match part {
+                    Some(part) => format!("Part {}:", part),
+                    None => "Parsing:".to_string(),
+                };
+                // a new run must not keep the zoom and frame of the previous one
+                let key = format!("{}-{}", progress.run, i);
+                html! { <Canvas {key} {label} grid={grid.clone()} /> }
+            }) }
+        </div>
+    }
+}
+
+fn view_step(step: &Step, now: f64) -> Html {
+    let name = match step.part {
+        Some(part) => format!("Part {}", part),
+        None => "Parsing".to_string(),
+    };
+    let status = match (&step.status, step.answer.as_ref()) {
+        (Status::Waiting, _) => "…".to_string(),
+        (Status::Running(since), _) => {
+            let frame = SPINNER[(now / 100.0) as usize % SPINNER.len()];
+            format!("{} {}", frame, format_time(now - since))
+        }
+        (Status::Done(time), Some(answer)) => format!("{} ({})", answer, format_time(*time)),
+        (Status::Done(time), None) => format_time(*time),
+        (Status::Stopped, _) => "-".to_string(),
+    };
+    html! {
+        <div>{ name }{ ": " }{ status }</div>
+    }
+}
@@ -0,0 +1,155 @@
+use std::rc::Rc;
+
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlInputElement};
+use yew::{
+    events::InputEvent, function_component, html, use_effect_with, use_node_ref, use_state, Html,
+    Properties, TargetCast,
+};
+
+use crate::aoc::output::{Frame, Grid};
+
+const BACKGROUND: &str = "#0f0f23";
+const FOREGROUND: &str = "#cccccc";
+/// Colors for all other characters than walls (`#` and `█`) and empty cells (` ` and `.`)
+const PALETTE: [&str; 6] = [
+    "#ffff66", "#00cc00", "#009900", "#ff6666", "#66ccff", "#cc66ff",
+];
+/// Width of the canvas for the default zoom
+const DEFAULT_WIDTH: usize = 600;
+const MAX_ZOOM: u32 = 32;
+
+#[derive(Properties)]
+pub struct CanvasProps {
+    pub label: String,
+    pub grid: Rc<Grid>,
+}
+
+impl PartialEq for CanvasProps {
+    fn eq(&self, other: &Self) -> bool {
+        // comparing all frames on every render would be too slow
+        self.label == other.label && Rc::ptr_eq(&self.grid, &other.grid)
+    }
+}
+
+/// Draws a grid of the debug output on a canvas, with every character as a square. Animations
+/// can be stepped through frame by frame.
+#[function_component]
+pub fn Canvas(props: &CanvasProps) -> Html {
+    let frames = &props.grid.frames;
+    let width = frames
+        .iter()
+        .flat_map(|frame| frame.lines.iter().map(|line| line.chars().count()))
+        .max()
+        .unwrap_or(0);
+    let zoom = use_state(|| (DEFAULT_WIDTH / width.max(1)).clamp(1, 16) as u32);
+    let index = use_state(|| 0);
+    let index_value = (*index).min(frames.len().saturating_sub(1));
+
+    let canvas_ref = use_node_ref();
+    {
+        let canvas_ref = canvas_ref.clone();
+        use_effect_with(
+            (props.grid.clone(), *zoom, index_value),
+            move |(grid, zoom, index)| {
+                let canvas = canvas_ref.cast::<HtmlCanvasElement>();
+                if let (Some(canvas), Some(frame)) = (canvas, grid.frames.get(*index)) {
+                    draw(&canvas, frame, *zoom);
+                }
+            },
+        );
+    }
+
+    let set_zoom = |zoom_value: u32| {
+        let zoom = zoom.clone();
+        move |_| zoom.set(zoom_value.clamp(1, MAX_ZOOM))
+    };
+    let set_index = |new_index: usize| {
+        let index = index.clone();
+        move |_| index.set(new_index)
+    };
+    let on_slide = {
+        let index = index.clone();
+        move |e: InputEvent| {
+            let slider: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = slider.value().parse() {
+                index.set(value);
+            }
+        }
+    };
+
+    let last = frames.len().saturating_sub(1);
+    let title = frames
+        .get(index_value)
+        .and_then(|frame| frame.title.clone());
+    // the last captured frame is the last frame of the animation, even if frames were dropped
+    let dropped = props.grid.dropped;
+    let number = if index_value == last {
+        index_value + dropped + 1
+    } else {
+        index_value + 1
+    };
+
+    html! {
+        <div>
+            { &props.label }{ " " }
+            <button onclick={set_zoom(*zoom / 2)} disabled={*zoom <= 1}>{ "−" }</button>
+            <button onclick={set_zoom(*zoom * 2)} disabled={*zoom >= MAX_ZOOM}>{ "+" }</button>
+            if last > 0 {
+                { " " }
+                <button onclick={set_index(index_value.saturating_sub(1))} disabled={index_value == 0}>{ "◀" }</button>
+                <input type="range" min="0" max={last.to_string()} value={index_value.to_string()} oninput={on_slide} />
+                <button onclick={set_index((index_value + 1).min(last))} disabled={index_value == last}>{ "▶" }</button>
+                { format!(" frame {}/{}", number, last + dropped + 1) }
+                if dropped > 0 {
+                    { format!(" ({} frames before the last one were dropped)", dropped) }
+                }
+            }
+            <div style="display: flex; align-items: center; gap: 1em">
+                <canvas ref={canvas_ref} />
+                if let Some(title) = title {
+                    <span style="font-size: 2em; font-family: monospace">{ title }</span>
+                }
+            </div>
+        </div>
+    }
+}
+
+fn draw(canvas: &HtmlCanvasElement, frame: &Frame, zoom: u32) {
+    let width = frame
+        .lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0) as u32;
+    canvas.set_width(width * zoom);
+    canvas.set_height(frame.lines.len() as u32 * zoom);
+
+    let context = canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|context| context.dyn_into::<CanvasRenderingContext2d>().ok());
+    let Some(context) = context else {
+        return;
+    };
+    let zoom = zoom as f64;
+    context.set_fill_style_str(BACKGROUND);
+    context.fill_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+    for (y, line) in frame.lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            if let Some(color) = cell_color(c) {
+                context.set_fill_style_str(color);
+                context.fill_rect(x as f64 * zoom, y as f64 * zoom, zoom, zoom);
+            }
+        }
+    }
+}
+
+fn cell_color(c: char) -> Option<&'static str> {
+    match c {
+        ' ' | '.' => None,
+        '#' | '█' => Some(FOREGROUND),
+        c => Some(PALETTE[c as usize % PALETTE.len()]),
+    }
+}
@@ -0,0 +1,42 @@
+//! Example inputs, embedded at build time from `input/<year>/example` by `build.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+/// Returns the example inputs of the day with a label, like `example` and `example 2` for
+/// `day<day>.txt` and `day<day>_2.txt`.
+pub fn examples_for(year: u16, day: u8) -> Vec<(String, &'static str)> {
+    EXAMPLES
+        .iter()
+        .filter(|(y, d, _, _)| *y == year && *d == day)
+        .map(|(_, _, suffix, input)| {
+            let label = if suffix.is_empty() {
+                "example".to_string()
+            } else {
+                format!("example {}", suffix.replace('_', "."))
+            };
+            (label, *input)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_for() {
+        let labels = |year, day| {
+            examples_for(year, day)
+                .into_iter()
+                .map(|(label, _)| label)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(2023, 1), vec!["example", "example 2"]);
+        assert_eq!(
+            labels(2023, 8),
+            vec!["example 1.1", "example 1.2", "example 2"]
+        );
+        assert!(labels(2023, 10).is_empty());
+        assert!(examples_for(2024, 1)[0].1.starts_with("3   4\n"));
+    }
+}
@@ -0,0 +1,122 @@
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{File, HtmlInputElement, HtmlTextAreaElement};
+use yew::{
+    events::{DragEvent, Event, InputEvent},
+    function_component, html, use_state, AttrValue, Callback, Html, Properties, TargetCast,
+};
+
+#[derive(Properties, PartialEq)]
+pub struct InputProps {
+    pub value: AttrValue,
+    /// Example inputs with their label, each gets a button to load it
+    pub examples: Vec<(String, &'static str)>,
+    pub onchange: Callback<String>,
+    /// Called after an example input was loaded
+    #[prop_or_default]
+    pub onexample: Callback<()>,
+}
+
+/// Text area for the input, which can also be loaded from a file (picker or drag and drop) or
+/// from one of the example inputs.
+#[function_component]
+pub fn Input(props: &InputProps) -> Html {
+    let dragging = use_state(|| false);
+    let error = use_state(|| None);
+
+    let read_file = {
+        let onchange = props.onchange.clone();
+        let error = error.clone();
+        Callback::from(move |file: File| {
+            let onchange = onchange.clone();
+            let error = error.clone();
+            spawn_local(async move {
+                let text = JsFuture::from(file.text()).await.ok();
+                match text.and_then(|text| text.as_string()) {
+                    Some(text) => {
+                        error.set(None);
+                        onchange.emit(text);
+                    }
+                    None => error.set(Some(format!("Can't read file {}", file.name()))),
+                }
+            });
+        })
+    };
+
+    let on_file = {
+        let read_file = read_file.clone();
+        move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                read_file.emit(file);
+            }
+            // allows to load the same file again after editing the input
+            input.set_value("");
+        }
+    };
+    let ondragover = {
+        let dragging = dragging.clone();
+        move |e: DragEvent| {
+            // required to allow dropping
+            e.prevent_default();
+            dragging.set(true);
+        }
+    };
+    let ondragleave = {
+        let dragging = dragging.clone();
+        move |_: DragEvent| dragging.set(false)
+    };
+    let ondrop = {
+        let dragging = dragging.clone();
+        move |e: DragEvent| {
+            e.prevent_default();
+            dragging.set(false);
+            let files = e.data_transfer().and_then(|data| data.files());
+            if let Some(file) = files.and_then(|files| files.get(0)) {
+                read_file.emit(file);
+            }
+        }
+    };
+    let oninput = {
+        let onchange = props.onchange.clone();
+        move |e: InputEvent| {
+            let textarea: HtmlTextAreaElement = e.target_unchecked_into();
+            onchange.emit(textarea.value());
+        }
+    };
+
+    let examples = props.examples.iter().map(|(label, example)| {
+        let onchange = props.onchange.clone();
+        let onexample = props.onexample.clone();
+        let example = example.to_string();
+        let onclick = move |_| {
+            onchange.emit(example.clone());
+            onexample.emit(());
+        };
+        html! {
+            <button {onclick}>{ "Load " }{ label }</button>
+        }
+    });
+
+    html! {
+        <div>
+            { "Input: " }
+            <input type="file" accept=".txt,text/plain" onchange={on_file} />
+            { for examples }
+            <br />
+            <textarea
+                rows="10"
+                cols="50"
+                placeholder="Paste the input or drop a file here"
+                style={if *dragging { "outline: 2px dashed" } else { "" }}
+                value={props.value.clone()}
+                {oninput}
+                {ondragover}
+                {ondragleave}
+                {ondrop}
+            />
+            if let Some(error) = error.as_ref() {
+                <div>{ "Error: " }{ error }</div>
+            }
+        </div>
+    }
+}
@@ -1,27 +1,243 @@
-use std::io::BufRead;
-
-use crate::aoc::{day::Day, part::Part};
-
-pub struct Results {
-    pub part1: Option<String>,
-    pub part2: Option<String>,
-}
-
-/// Runs the given part(s) for the given day and returns the results.
-pub fn run(day: Day, part: &Part, input: &mut dyn BufRead) -> Results {
-    let solution = day.parse(input);
-    match part {
-        Part::Part1 => Results {
-            part1: Some(part.run_for(solution.as_ref())),
-            part2: None,
-        },
-        Part::Part2 => Results {
-            part1: None,
-            part2: Some(part.run_for(solution.as_ref())),
-        },
-        Part::Both => Results {
-            part1: Some(Part::Part1.run_for(solution.as_ref())),
-            part2: Some(Part::Part2.run_for(solution.as_ref())),
-        },
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{ErrorEvent, MessageEvent, Worker};
+use yew::{Callback, Reducible};
+
+use crate::aoc::{output::Grid, part::Part};
+
+use super::worker::{Request, Response};
+
+/// Loader of the worker created by trunk, see `www/index.html`.
+const WORKER_PATH: &str = "./wasm_worker_loader.js";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Waiting,
+    /// Running since the timestamp in milliseconds
+    Running(f64),
+    /// Done in the time in milliseconds
+    Done(f64),
+    Stopped,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    /// `None` for parsing the input
+    pub part: Option<Part>,
+    pub status: Status,
+    pub answer: Option<String>,
+}
+
+/// Progress of the current run: parsing and the requested parts.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Progress {
+    /// Counts the runs, so the grids of different runs are different components
+    pub run: u32,
+    pub year: u16,
+    pub day: u8,
+    pub steps: Vec<Step>,
+    /// Grids printed while parsing (without part) or solving a part
+    pub grids: Vec<(Option<Part>, Rc<Grid>)>,
+    pub error: Option<String>,
+    /// Current time in milliseconds, updated while running
+    pub now: f64,
+}
+
+pub enum Action {
+    Start { year: u16, day: u8, part: Part },
+    Response(Response),
+    Cancel,
+    Clear,
+    Tick,
+}
+
+impl Progress {
+    pub fn is_running(&self) -> bool {
+        self.steps
+            .iter()
+            .any(|step| matches!(step.status, Status::Waiting | Status::Running(_)))
+    }
+
+    /// Finishes the running step and starts the next one.
+    fn finish_step(&mut self, part: Option<Part>, time: f64, answer: Option<String>) {
+        let now = js_sys::Date::now();
+        if let Some(step) = self.steps.iter_mut().find(|step| step.part == part) {
+            step.status = Status::Done(time);
+            step.answer = answer;
+        }
+        if let Some(next) = self
+            .steps
+            .iter_mut()
+            .find(|step| step.status == Status::Waiting)
+        {
+            next.status = Status::Running(now);
+        }
+        self.now = now;
+    }
+
+    fn stop(&mut self, error: Option<String>) {
+        for step in self.steps.iter_mut() {
+            if matches!(step.status, Status::Waiting | Status::Running(_)) {
+                step.status = Status::Stopped;
+            }
+        }
+        self.error = error;
+    }
+}
+
+impl Reducible for Progress {
+    type Action = Action;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        let mut progress = (*self).clone();
+        match action {
+            Action::Start { year, day, part } => {
+                let now = js_sys::Date::now();
+                let parts = match part {
+                    Part::Both => vec![Part::Part1, Part::Part2],
+                    part => vec![part],
+                };
+                let step = |part, status| Step {
+                    part,
+                    status,
+                    answer: None,
+                };
+                progress = Progress {
+                    run: progress.run + 1,
+                    year,
+                    day,
+                    steps: [step(None, Status::Running(now))]
+                        .into_iter()
+                        .chain(
+                            parts
+                                .into_iter()
+                                .map(|part| step(Some(part), Status::Waiting)),
+                        )
+                        .collect(),
+                    grids: vec![],
+                    error: None,
+                    now,
+                };
+            }
+            Action::Response(Response::Ready) => return self,
+            Action::Response(Response::Parsed { time }) => progress.finish_step(None, time, None),
+            Action::Response(Response::Answer { part, answer, time }) => {
+                progress.finish_step(Some(part), time, Some(answer))
+            }
+            Action::Response(Response::Grids { part, grids }) => progress
+                .grids
+                .extend(grids.into_iter().map(|grid| (part, Rc::new(grid)))),
+            Action::Response(Response::Error(error)) => {
+                // a panic is reported twice, by the panic hook and the error of the worker
+                if !progress.is_running() {
+                    return self;
+                }
+                progress.stop(Some(error));
+            }
+            Action::Cancel => progress.stop(Some("Cancelled".to_string())),
+            Action::Clear => {
+                progress = Progress {
+                    run: progress.run,
+                    ..Progress::default()
+                }
+            }
+            Action::Tick => progress.now = js_sys::Date::now(),
+        }
+        progress.into()
+    }
+}
+
+/// Web Worker which runs the solutions, it's terminated when dropped.
+pub struct Runner {
+    worker: Worker,
+    /// The request is sent when the worker is ready
+    pending: Rc<RefCell<Option<Request>>>,
+    ready: Rc<Cell<bool>>,
+    failed: Rc<Cell<bool>>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    _onerror: Closure<dyn FnMut(ErrorEvent)>,
+}
+
+impl Runner {
+    pub fn new(on_response: Callback<Response>) -> Result<Self, String> {
+        let worker =
+            Worker::new(WORKER_PATH).map_err(|error| format!("Can't start worker: {:?}", error))?;
+        let pending = Rc::new(RefCell::new(None::<Request>));
+        let ready = Rc::new(Cell::new(false));
+        let failed = Rc::new(Cell::new(false));
+
+        let onmessage = Closure::<dyn FnMut(MessageEvent)>::new({
+            let worker = worker.clone();
+            let pending = pending.clone();
+            let ready = ready.clone();
+            let failed = failed.clone();
+            let on_response = on_response.clone();
+            move |event: MessageEvent| {
+                let Some(response) = Response::from_js(&event.data()) else {
+                    return;
+                };
+                match response {
+                    Response::Ready => {
+                        ready.set(true);
+                        if let Some(request) = pending.borrow_mut().take() {
+                            let _ = worker.post_message(&request.to_js());
+                        }
+                    }
+                    Response::Error(_) => failed.set(true),
+                    _ => {}
+                }
+                on_response.emit(response);
+            }
+        });
+        let onerror = Closure::<dyn FnMut(ErrorEvent)>::new({
+            let failed = failed.clone();
+            move |event: ErrorEvent| {
+                failed.set(true);
+                on_response.emit(Response::Error(event.message()));
+            }
+        });
+        worker.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        worker.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        Ok(Self {
+            worker,
+            pending,
+            ready,
+            failed,
+            _onmessage: onmessage,
+            _onerror: onerror,
+        })
+    }
+
+    /// Returns false if the worker failed and a new one is needed.
+    pub fn is_usable(&self) -> bool {
+        !self.failed.get()
+    }
+
+    pub fn run(&self, request: Request) {
+        if self.ready.get() {
+            let _ = self.worker.post_message(&request.to_js());
+        } else {
+            *self.pending.borrow_mut() = Some(request);
+        }
+    }
+}
+
+impl Drop for Runner {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+/// Formats a duration in milliseconds.
+pub fn format_time(ms: f64) -> String {
+    if ms < 1000.0 {
+        format!("{:.2} ms", ms)
+    } else {
+        format!("{:.2} s", ms / 1000.0)
     }
 }
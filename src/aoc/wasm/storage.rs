@@ -0,0 +1,29 @@
+//! Persists the last input of each day in the `localStorage` of the browser.
+
+use web_sys::Storage;
+
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+fn input_key(year: u16, day: u8) -> String {
+    format!("aoc-input-{}-{}", year, day)
+}
+
+/// Returns the last input of the day, or `None` if there is none or storage isn't available.
+pub fn load_input(year: u16, day: u8) -> Option<String> {
+    local_storage()?.get_item(&input_key(year, day)).ok()?
+}
+
+/// Stores the input of the day, an empty input removes it. Errors (like a full or disabled
+/// storage) are ignored, the input just isn't restored after a reload.
+pub fn save_input(year: u16, day: u8, input: &str) {
+    if let Some(storage) = local_storage() {
+        let key = input_key(year, day);
+        let _ = if input.is_empty() {
+            storage.remove_item(&key)
+        } else {
+            storage.set_item(&key, input)
+        };
+    }
+}
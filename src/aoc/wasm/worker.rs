@@ -0,0 +1,255 @@
+//! Web Worker (`src/bin/wasm_worker.rs`) which runs the solutions, so slow solutions don't freeze
+//! the page and can be cancelled by terminating the worker.
+//!
+//! Messages are plain JS arrays with the type as first element.
+
+use std::panic;
+
+use js_sys::Array;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
+
+use crate::{
+    aoc::{
+        cli::run::panic_message,
+        output::{self, Frame, Grid},
+        part::Part,
+    },
+    common::animation,
+    Solutions,
+};
+
+/// Message from the page to the worker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub params: Vec<String>,
+    pub input: String,
+    /// Whether frames of animations are captured
+    pub animate: bool,
+}
+
+/// Message from the worker to the page, times are in milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Response {
+    /// The worker is loaded and accepts requests
+    Ready,
+    Parsed {
+        time: f64,
+    },
+    Answer {
+        part: Part,
+        answer: String,
+        time: f64,
+    },
+    /// Grids printed while parsing (without part) or solving a part
+    Grids {
+        part: Option<Part>,
+        grids: Vec<Grid>,
+    },
+    /// The run failed, after a panic the worker can't be used anymore
+    Error(String),
+}
+
+impl Request {
+    pub fn to_js(&self) -> JsValue {
+        let params = self.params.iter().map(JsValue::from).collect::<Array>();
+        let message = Array::of5(
+            &self.year.into(),
+            &self.day.into(),
+            &self.part.to_string().into(),
+            &params,
+            &self.input.as_str().into(),
+        );
+        message.push(&self.animate.into());
+        message.into()
+    }
+
+    pub fn from_js(value: &JsValue) -> Option<Self> {
+        let message = value.dyn_ref::<Array>()?;
+        Some(Self {
+            year: message.get(0).as_f64()? as u16,
+            day: message.get(1).as_f64()? as u8,
+            part: message.get(2).as_string()?.parse().ok()?,
+            params: message
+                .get(3)
+                .dyn_ref::<Array>()?
+                .iter()
+                .filter_map(|param| param.as_string())
+                .collect(),
+            input: message.get(4).as_string()?,
+            animate: message.get(5).as_bool()?,
+        })
+    }
+}
+
+impl Response {
+    pub fn to_js(&self) -> JsValue {
+        match self {
+            Response::Ready => Array::of1(&"ready".into()),
+            Response::Parsed { time } => Array::of2(&"parsed".into(), &(*time).into()),
+            Response::Answer { part, answer, time } => Array::of4(
+                &"answer".into(),
+                &part.to_string().into(),
+                &answer.as_str().into(),
+                &(*time).into(),
+            ),
+            Response::Grids { part, grids } => {
+                let grids = grids.iter().map(grid_to_js).collect::<Array>();
+                let part = part.map_or(JsValue::NULL, |part| part.to_string().into());
+                Array::of3(&"grids".into(), &part, &grids)
+            }
+            Response::Error(error) => Array::of2(&"error".into(), &error.as_str().into()),
+        }
+        .into()
+    }
+
+    pub fn from_js(value: &JsValue) -> Option<Self> {
+        let message = value.dyn_ref::<Array>()?;
+        match message.get(0).as_string()?.as_str() {
+            "ready" => Some(Response::Ready),
+            "parsed" => Some(Response::Parsed {
+                time: message.get(1).as_f64()?,
+            }),
+            "answer" => Some(Response::Answer {
+                part: message.get(1).as_string()?.parse().ok()?,
+                answer: message.get(2).as_string()?,
+                time: message.get(3).as_f64()?,
+            }),
+            "grids" => Some(Response::Grids {
+                part: match message.get(1).as_string() {
+                    Some(part) => Some(part.parse().ok()?),
+                    None => None,
+                },
+                grids: message
+                    .get(2)
+                    .dyn_ref::<Array>()?
+                    .iter()
+                    .map(grid_from_js)
+                    .collect::<Option<_>>()?,
+            }),
+            "error" => Some(Response::Error(message.get(1).as_string()?)),
+            _ => None,
+        }
+    }
+}
+
+fn grid_to_js(grid: &Grid) -> JsValue {
+    let frames = grid.frames.iter().map(frame_to_js).collect::<Array>();
+    Array::of2(&frames, &(grid.dropped as f64).into()).into()
+}
+
+fn grid_from_js(value: JsValue) -> Option<Grid> {
+    let grid = value.dyn_ref::<Array>()?;
+    Some(Grid {
+        frames: grid
+            .get(0)
+            .dyn_ref::<Array>()?
+            .iter()
+            .map(frame_from_js)
+            .collect::<Option<_>>()?,
+        dropped: grid.get(1).as_f64()? as usize,
+    })
+}
+
+fn frame_to_js(frame: &Frame) -> JsValue {
+    let title = frame.title.as_deref().map_or(JsValue::NULL, JsValue::from);
+    let lines = frame.lines.iter().map(JsValue::from).collect::<Array>();
+    Array::of2(&title, &lines).into()
+}
+
+fn frame_from_js(value: JsValue) -> Option<Frame> {
+    let frame = value.dyn_ref::<Array>()?;
+    Some(Frame {
+        title: frame.get(0).as_string(),
+        lines: frame
+            .get(1)
+            .dyn_ref::<Array>()?
+            .iter()
+            .map(|line| line.as_string())
+            .collect::<Option<_>>()?,
+    })
+}
+
+/// Starts handling requests, called from the `main` of the worker.
+pub fn start() {
+    let scope = js_sys::global().unchecked_into::<DedicatedWorkerGlobalScope>();
+
+    // panics abort in wasm, so the page has to be told why the worker stopped
+    panic::set_hook(Box::new({
+        let scope = scope.clone();
+        move |info| {
+            let error = format!("Solution failed: {}", panic_message(info.payload()));
+            post(&scope, Response::Error(error));
+        }
+    }));
+
+    let onmessage = Closure::<dyn FnMut(MessageEvent)>::new({
+        let scope = scope.clone();
+        move |event: MessageEvent| {
+            if let Some(request) = Request::from_js(&event.data()) {
+                run(request, |response| post(&scope, response));
+            }
+        }
+    });
+    scope.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+    // the handler lives as long as the worker
+    onmessage.forget();
+
+    post(&scope, Response::Ready);
+}
+
+fn post(scope: &DedicatedWorkerGlobalScope, response: Response) {
+    let _ = scope.post_message(&response.to_js());
+}
+
+/// Runs the requested part(s) and posts the answer and the grids of each part as soon as it's
+/// ready.
+fn run(request: Request, post: impl Fn(Response)) {
+    let day = match Solutions::get(request.year, request.day).with_params(&request.params) {
+        Ok(day) => day,
+        Err(error) => return post(Response::Error(error)),
+    };
+    if request.animate {
+        animation::enable_capture();
+    } else {
+        animation::disable();
+    }
+    let post_grids = |part| {
+        animation::finish();
+        let grids = output::take_grids();
+        if !grids.is_empty() {
+            post(Response::Grids { part, grids });
+        }
+    };
+
+    output::capture_grids();
+    let start = now();
+    let solution = day.parse(&request.input);
+    let time = now() - start;
+    post(Response::Parsed { time });
+    post_grids(None);
+
+    let parts = match request.part {
+        Part::Both => vec![Part::Part1, Part::Part2],
+        part => vec![part],
+    };
+    for part in parts {
+        output::capture_grids();
+        let start = now();
+        let answer = part.run_for(solution.as_ref());
+        let time = now() - start;
+        post(Response::Answer { part, answer, time });
+        post_grids(Some(part));
+    }
+}
+
+/// Milliseconds with sub-millisecond precision, `std::time::Instant` isn't available in wasm.
+fn now() -> f64 {
+    js_sys::global()
+        .unchecked_into::<DedicatedWorkerGlobalScope>()
+        .performance()
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
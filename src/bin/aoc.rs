@@ -1,4 +1,4 @@
-use std::io::{self, BufReader};
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
 
@@ -14,12 +14,19 @@ use adventofcode::{
             print_results::{run_and_print_results, PrintResults},
             run::{Run, RunFunction},
         },
+        day::Day,
         input, output,
         part::Part,
     },
+    common::{animation, viz},
     Solutions,
 };
 
+#[cfg(feature = "serve")]
+use adventofcode::aoc::cli::serve;
+#[cfg(feature = "tui")]
+use adventofcode::aoc::cli::tui;
+
 fn main() {
     let cli = Cli::parse();
     cli.color.set_color_mode_override();
@@ -35,18 +42,67 @@ fn main() {
                     join(Solutions::days_for_year(year), ", ")
                 );
             }
+
+            println!("Parameters (override with `--param <name>=<value>`):");
+            for year in Solutions::years() {
+                for day in Solutions::days_for_year(year) {
+                    for param in Solutions::get(year, day).params {
+                        let example = param
+                            .example
+                            .map(|example| format!(", {} for the example", example))
+                            .unwrap_or_default();
+                        println!(
+                            "\t{} day {:>2}: {}={} ({}{})",
+                            year.to_string().white(),
+                            day,
+                            param.name.white(),
+                            param.default,
+                            param.description,
+                            example
+                        );
+                    }
+                }
+            }
         }
 
-        Commands::Run(args) => run_solutions(PrintResults::run, args),
+        Commands::Run(args) => {
+            #[cfg(feature = "animate")]
+            if args.animate {
+                animation::enable(args.fps);
+            }
+
+            run_solutions(PrintResults::run, args)
+        }
 
         Commands::Bench(args) => {
             if Some("-".to_string()) == args.input {
                 exit_error("Benchmarking from stdin is not supported".to_string())
             }
+            #[cfg(feature = "animate")]
+            if args.animate {
+                exit_error("Animations are not supported when benchmarking".to_string())
+            }
+            if args.viz.is_some() {
+                exit_error("Visualisations are not supported when benchmarking".to_string())
+            }
 
             output::disable_output();
             run_solutions(Benchmark::run, args)
         }
+
+        #[cfg(feature = "tui")]
+        Commands::Tui => {
+            if let Err(e) = tui::run() {
+                exit_error(e)
+            }
+        }
+
+        #[cfg(feature = "serve")]
+        Commands::Serve(args) => {
+            if let Err(e) = serve::run(&args.host, args.port) {
+                exit_error(e)
+            }
+        }
     }
 }
 
@@ -57,14 +113,25 @@ fn run_solutions(run: RunFunction, args: &RunArgs) {
             match &args.input {
                 Some(stdin) if stdin == "-" => {
                     // Handle stdin input, can only happen with normal `run` command
-                    let mut stdin = BufReader::new(io::stdin());
-                    let day = Solutions::get(args.year, day);
-                    run_and_print_results(day, &args.part, &mut stdin);
+                    let stdin = io::read_to_string(io::stdin()).unwrap_or_else(|error| {
+                        exit_error(format!("Error reading input from stdin: {}", error))
+                    });
+                    let day = get_day(args.year, day, &args.params);
+                    set_viz_dir(args, day.day);
+                    run_and_print_results(day, &args.part, &stdin);
+                    animation::finish();
                 }
                 input => {
                     let input =
                         input_path_or_default(args.year, day, input.clone(), args.download());
-                    run_solution(args.year, day, &args.part, &input, run);
+                    set_viz_dir(args, day);
+                    run_solution(
+                        get_day(args.year, day, &args.params),
+                        &args.part,
+                        &input,
+                        run,
+                    );
+                    animation::finish();
                 }
             };
         } else {
@@ -77,13 +144,23 @@ fn run_solutions(run: RunFunction, args: &RunArgs) {
         }
     } else {
         output::disable_debug();
+        animation::disable();
         for day in all_days {
             let input = input_path_or_default(args.year, day, None, args.download());
-            run_solution(args.year, day, &args.part, &input, run);
+            set_viz_dir(args, day);
+            run_solution(Solutions::get(args.year, day), &args.part, &input, run);
         }
     }
 }
 
+fn set_viz_dir(args: &RunArgs, day: u8) {
+    viz::set_output_dir(
+        args.viz
+            .as_ref()
+            .map(|dir| dir.join(args.year.to_string()).join(format!("day{}", day))),
+    );
+}
+
 fn input_path_or_default(year: u16, day: u8, input: Option<String>, download: bool) -> PathBuf {
     input.map(PathBuf::from).unwrap_or_else(|| {
         match input::get_default_input_path(year, day, download) {
@@ -93,8 +170,14 @@ fn input_path_or_default(year: u16, day: u8, input: Option<String>, download: bo
     })
 }
 
-fn run_solution(year: u16, day: u8, part: &Part, path: &Path, run: RunFunction) {
-    let day = Solutions::get(year, day);
+fn get_day(year: u16, day: u8, params: &[String]) -> Day {
+    match Solutions::get(year, day).with_params(params) {
+        Ok(day) => day,
+        Err(e) => exit_error(e),
+    }
+}
+
+fn run_solution(day: Day, part: &Part, path: &Path, run: RunFunction) {
     match run(day, part, path) {
         Ok(()) => {}
         Err(e) => exit_error(e),
@@ -1,3 +1,14 @@
+pub mod animation;
+pub mod bitgrid;
+pub mod cycle;
+pub mod dsu;
+pub mod graph;
 pub mod grid;
+pub mod hex;
+pub mod linalg;
+pub mod math;
+pub mod memo;
 pub mod ocr;
 pub mod parsing;
+pub mod ranges;
+pub mod viz;
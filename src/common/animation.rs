@@ -0,0 +1,337 @@
+//! Terminal animations of simulations.
+//!
+//! Solutions call [`frame`] (or [`frame_area`]) for every step of a simulation. When animations
+//! are enabled (with `aoc run --animate`), every frame is drawn over the previous one with a
+//! frame counter, and can be paused, stepped through and sped up with the keyboard:
+//!
+//! - `space`: pause and resume
+//! - `n` or `→`: show the next frame while paused
+//! - `+` and `-`: double or halve the frame rate
+//! - `q` or `esc`: skip the rest of the animation
+//!
+//! When animations are disabled the render closures aren't called, so frames cost nothing.
+//! The web app enables them with [`enable_capture`] and draws the frames captured with
+//! [`output::capture_grids`] instead.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use glam::IVec2;
+
+use crate::aoc::output;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Shows a frame if animations are enabled, `render` returns the lines of the frame.
+///
+/// ```
+/// # use adventofcode::common::animation;
+/// for step in 0..3 {
+///     animation::frame(|| vec![format!("step {}", step)]);
+/// }
+/// ```
+pub fn frame<F>(render: F)
+where
+    F: FnOnce() -> Vec<String>,
+{
+    if is_enabled() {
+        show(None, render());
+    }
+}
+
+/// Shows a frame of an area (inclusive) if animations are enabled, like [`super::grid::print_area`].
+pub fn frame_area<F>(area: (IVec2, IVec2), map_pos: F)
+where
+    F: Fn(IVec2) -> String,
+{
+    frame(|| render_area(area, map_pos));
+}
+
+/// Same as [`frame_area`] with an additional title line above the area.
+pub fn frame_area_with_title<F>(title: String, area: (IVec2, IVec2), map_pos: F)
+where
+    F: Fn(IVec2) -> String,
+{
+    if is_enabled() {
+        show(Some(title), render_area(area, map_pos));
+    }
+}
+
+fn show(title: Option<String>, lines: Vec<String>) {
+    if output::is_capturing_grids() {
+        output::push_frame(title, lines);
+        return;
+    }
+
+    #[cfg(feature = "animate")]
+    player::show(title.into_iter().chain(lines).collect());
+
+    #[cfg(not(feature = "animate"))]
+    let _ = (title, lines);
+}
+
+fn render_area<F>(area: (IVec2, IVec2), map_pos: F) -> Vec<String>
+where
+    F: Fn(IVec2) -> String,
+{
+    (area.0.y..=area.1.y)
+        .map(|y| {
+            (area.0.x..=area.1.x)
+                .map(|x| map_pos(IVec2::new(x, y)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Enables animations with the given frame rate.
+#[cfg(feature = "animate")]
+pub fn enable(fps: u32) {
+    player::init(fps);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Enables animations without the terminal player, the frames are only collected with
+/// [`output::capture_grids`].
+pub fn enable_capture() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Disables animations, frames aren't rendered anymore.
+pub fn disable() {
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Ends the current animation, the last frame stays visible.
+/// Animations stay enabled, the next frame starts a new animation.
+pub fn finish() {
+    output::finish_frames();
+
+    #[cfg(feature = "animate")]
+    player::finish();
+}
+
+#[cfg(feature = "animate")]
+mod player {
+    use std::{
+        io::{self, Write},
+        sync::{atomic::Ordering, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use crossterm::{
+        cursor,
+        event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+        queue,
+        style::Print,
+        terminal::{self, Clear, ClearType},
+    };
+
+    use super::ENABLED;
+
+    const MAX_FPS: u32 = 1000;
+
+    struct Player {
+        fps: u32,
+        frame: usize,
+        paused: bool,
+        /// Whether the screen was cleared for the current animation
+        active: bool,
+        last_frame: Option<Instant>,
+        height: u16,
+    }
+
+    static PLAYER: Mutex<Player> = Mutex::new(Player {
+        fps: 10,
+        frame: 0,
+        paused: false,
+        active: false,
+        last_frame: None,
+        height: 0,
+    });
+
+    pub fn init(fps: u32) {
+        PLAYER.lock().unwrap().fps = fps.clamp(1, MAX_FPS);
+
+        // don't leave the terminal in raw mode if a solution panics
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let _ = terminal::disable_raw_mode();
+            hook(info);
+        }));
+    }
+
+    pub fn show(lines: Vec<String>) {
+        let mut player = PLAYER.lock().unwrap();
+        if !player.active {
+            player.active = true;
+            player.frame = 0;
+            player.paused = false;
+            let _ = queue!(io::stdout(), Clear(ClearType::All));
+        }
+
+        player.frame += 1;
+        let _ = player.draw(&lines);
+        player.wait_for_next_frame();
+    }
+
+    pub fn finish() {
+        if let Ok(mut player) = PLAYER.lock() {
+            if player.active {
+                player.active = false;
+                let mut stdout = io::stdout();
+                let _ = queue!(stdout, cursor::Show);
+                let _ = stdout.flush();
+            }
+        }
+    }
+
+    impl Player {
+        fn draw(&mut self, lines: &[String]) -> io::Result<()> {
+            let mut stdout = io::stdout();
+            queue!(stdout, cursor::Hide, cursor::MoveTo(0, 0))?;
+            // frames taller than the terminal would scroll, so only the top is shown
+            let rows = match terminal::size() {
+                Ok((_, rows)) if rows > 0 => rows,
+                _ => u16::MAX,
+            };
+            let lines = &lines[..lines.len().min(rows.saturating_sub(2) as usize)];
+            for line in lines {
+                queue!(
+                    stdout,
+                    Print(line),
+                    Clear(ClearType::UntilNewLine),
+                    Print("\r\n")
+                )?;
+            }
+            self.height = lines.len() as u16;
+            self.draw_status(&mut stdout)?;
+            // other output goes below the frame until the next frame clears it
+            queue!(stdout, Print("\r\n"), Clear(ClearType::FromCursorDown))?;
+            stdout.flush()
+        }
+
+        fn draw_status(&self, stdout: &mut io::Stdout) -> io::Result<()> {
+            queue!(
+                stdout,
+                cursor::MoveTo(0, self.height),
+                Print(format!(
+                    "frame {} | {} fps{} | [space] pause  [n] step  [+/-] speed  [q] skip",
+                    self.frame,
+                    self.fps,
+                    if self.paused { " | paused" } else { "" }
+                )),
+                Clear(ClearType::UntilNewLine)
+            )
+        }
+
+        /// Handles key presses until the next frame is due (or requested while paused).
+        /// The terminal is only in raw mode while waiting, so other output isn't affected.
+        fn wait_for_next_frame(&mut self) {
+            let interactive = terminal::enable_raw_mode().is_ok();
+
+            loop {
+                let next_frame = self.last_frame.unwrap_or_else(Instant::now)
+                    + Duration::from_secs(1) / self.fps;
+                let timeout = next_frame.saturating_duration_since(Instant::now());
+
+                if !interactive {
+                    std::thread::sleep(timeout);
+                    break;
+                }
+                let timeout = if self.paused {
+                    Duration::from_secs(3600)
+                } else {
+                    timeout
+                };
+                match event::poll(timeout) {
+                    Ok(true) => {}
+                    _ => break,
+                }
+                let Ok(Event::Key(key)) = event::read() else {
+                    continue;
+                };
+                if key.kind == KeyEventKind::Release {
+                    continue;
+                }
+
+                match key {
+                    KeyEvent {
+                        code: KeyCode::Char('c'),
+                        modifiers: KeyModifiers::CONTROL,
+                        ..
+                    } => {
+                        // raw mode doesn't send SIGINT
+                        let _ = terminal::disable_raw_mode();
+                        let _ = queue!(io::stdout(), cursor::Show, Print("\r\n"));
+                        let _ = io::stdout().flush();
+                        std::process::exit(130);
+                    }
+                    KeyEvent {
+                        code: KeyCode::Char('q') | KeyCode::Esc,
+                        ..
+                    } => {
+                        ENABLED.store(false, Ordering::Relaxed);
+                        break;
+                    }
+                    KeyEvent {
+                        code: KeyCode::Char(' '),
+                        ..
+                    } => self.paused = !self.paused,
+                    KeyEvent {
+                        code: KeyCode::Char('n') | KeyCode::Right,
+                        ..
+                    } if self.paused => break,
+                    KeyEvent {
+                        code: KeyCode::Char('+'),
+                        ..
+                    } => self.fps = (self.fps * 2).min(MAX_FPS),
+                    KeyEvent {
+                        code: KeyCode::Char('-'),
+                        ..
+                    } => self.fps = (self.fps / 2).max(1),
+                    _ => {}
+                }
+
+                let mut stdout = io::stdout();
+                let _ = self.draw_status(&mut stdout);
+                let _ = queue!(stdout, cursor::MoveTo(0, self.height + 1));
+                let _ = stdout.flush();
+            }
+
+            if interactive {
+                let _ = terminal::disable_raw_mode();
+            }
+            self.last_frame = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_frames_are_not_rendered() {
+        assert!(!is_enabled());
+        frame(|| panic!("frame rendered while animations are disabled"));
+        frame_area((IVec2::ZERO, IVec2::ONE), |_| {
+            panic!("area rendered while animations are disabled")
+        });
+        finish();
+    }
+
+    #[test]
+    fn test_render_area() {
+        let lines = render_area((IVec2::new(-1, 0), IVec2::new(1, 1)), |p| {
+            if p.x == p.y {
+                "#".to_string()
+            } else {
+                ".".to_string()
+            }
+        });
+        assert_eq!(lines, [".#.", "..#"].map(String::from));
+    }
+}
@@ -0,0 +1,523 @@
+//! Sets of grid positions stored as bitmasks, one row of `u64` words per line.
+//!
+//! Whole grids are combined with bit operations (`&`, `|`, `^`, `!`) and moved with
+//! [`BitGrid::shifted`], which makes cellular automata and simulations of many positions
+//! much faster than a `HashSet<IVec2>`. Positions are in `0..width` and `0..height`,
+//! everything moved outside of the grid is dropped.
+
+use std::{
+    fmt::{self, Display},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, RangeInclusive},
+};
+
+use glam::IVec2;
+
+use super::grid::directions::NEIGHBORS;
+
+/// A fixed width set of grid positions.
+///
+/// ```
+/// # use adventofcode::common::bitgrid::BitGrid;
+/// # use glam::IVec2;
+/// let mut grid = BitGrid::new(5, 3);
+/// grid.insert(IVec2::new(1, 1));
+/// grid.insert(IVec2::new(4, 2));
+/// let moved = grid.shifted(IVec2::new(1, 0));
+/// assert!(moved.contains(IVec2::new(2, 1)));
+/// assert_eq!(moved.len(), 1);
+/// assert_eq!((&grid | &moved).to_string(), ".....\n.##..\n....#");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    /// Words per row
+    stride: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let stride = width.div_ceil(64);
+        Self {
+            width,
+            height,
+            stride,
+            words: vec![0; stride * height],
+        }
+    }
+
+    /// Creates a grid with the positions, panics if a position is outside of the grid.
+    pub fn from_positions(
+        width: usize,
+        height: usize,
+        positions: impl IntoIterator<Item = IVec2>,
+    ) -> Self {
+        let mut grid = Self::new(width, height);
+        for pos in positions {
+            grid.insert(pos);
+        }
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: IVec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    pub fn contains(&self, pos: IVec2) -> bool {
+        self.in_bounds(pos) && {
+            let (index, bit) = self.index(pos);
+            self.words[index] & bit != 0
+        }
+    }
+
+    /// Adds the position, returns false if it was already set.
+    pub fn insert(&mut self, pos: IVec2) -> bool {
+        assert!(
+            self.in_bounds(pos),
+            "Position {} is outside of the {}x{} grid",
+            pos,
+            self.width,
+            self.height
+        );
+        let (index, bit) = self.index(pos);
+        let new = self.words[index] & bit == 0;
+        self.words[index] |= bit;
+        new
+    }
+
+    /// Removes the position, returns false if it wasn't set.
+    pub fn remove(&mut self, pos: IVec2) -> bool {
+        if !self.contains(pos) {
+            return false;
+        }
+        let (index, bit) = self.index(pos);
+        self.words[index] &= !bit;
+        true
+    }
+
+    /// Number of positions in the grid.
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Returns the positions line by line.
+    pub fn iter(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let y = (index / self.stride) as i32;
+                let x = (index % self.stride * 64) as i32;
+                BitIter(word).map(move |bit| IVec2::new(x + bit as i32, y))
+            })
+    }
+
+    /// Returns the words of a line, bit `x % 64` of word `x / 64` is position `x`.
+    pub fn row(&self, y: usize) -> &[u64] {
+        &self.words[y * self.stride..(y + 1) * self.stride]
+    }
+
+    /// Changes the number of lines, new lines are added (or removed) at the end.
+    pub fn resize(&mut self, height: usize) {
+        self.height = height;
+        self.words.resize(self.stride * height, 0);
+    }
+
+    /// Returns the smallest and largest x and y of all positions, or `None` if the grid is empty.
+    pub fn bounding_box(&self) -> Option<(IVec2, IVec2)> {
+        let is_empty = |y: &usize| self.row(*y).iter().all(|&word| word == 0);
+        let min_y = (0..self.height).find(|y| !is_empty(y))?;
+        let max_y = (0..self.height).rfind(|y| !is_empty(y))?;
+
+        // combine all lines to find the columns
+        let mut columns = vec![0; self.stride];
+        for y in min_y..=max_y {
+            for (column, word) in columns.iter_mut().zip(self.row(y)) {
+                *column |= word;
+            }
+        }
+        let first = columns.iter().position(|&word| word != 0)?;
+        let last = columns.iter().rposition(|&word| word != 0)?;
+        let min_x = first * 64 + columns[first].trailing_zeros() as usize;
+        let max_x = last * 64 + 63 - columns[last].leading_zeros() as usize;
+
+        Some((
+            IVec2::new(min_x as i32, min_y as i32),
+            IVec2::new(max_x as i32, max_y as i32),
+        ))
+    }
+
+    /// Returns a grid where every position is moved by `offset`.
+    pub fn shifted(&self, offset: IVec2) -> BitGrid {
+        let mut shifted = Self::new(self.width, self.height);
+        shifted.insert_grid(self, offset);
+        shifted
+    }
+
+    /// Returns a bigger grid with `margin` empty lines and columns on every side.
+    pub fn expanded(&self, margin: usize) -> BitGrid {
+        let mut expanded = Self::new(self.width + 2 * margin, self.height + 2 * margin);
+        expanded.insert_grid(self, IVec2::splat(margin as i32));
+        expanded
+    }
+
+    /// Adds all positions of `other` moved by `offset`, positions outside of the grid are dropped.
+    pub fn insert_grid(&mut self, other: &BitGrid, offset: IVec2) {
+        let mask = self.last_word_mask();
+        for y in 0..other.height {
+            let Some(target) = self.target_row(y, offset) else {
+                continue;
+            };
+            let source = other.row(y);
+            let row = &mut self.words[target * self.stride..(target + 1) * self.stride];
+            for (i, word) in row.iter_mut().enumerate() {
+                *word |= bits_at(source, i as i64 * 64 - offset.x as i64);
+            }
+            if let Some(last) = row.last_mut() {
+                *last &= mask;
+            }
+        }
+    }
+
+    /// Returns true if any position of `other` moved by `offset` is in this grid.
+    ///
+    /// ```
+    /// # use adventofcode::common::bitgrid::BitGrid;
+    /// # use glam::IVec2;
+    /// let grid = BitGrid::from_positions(4, 4, [IVec2::new(2, 3)]);
+    /// let piece = BitGrid::from_positions(2, 2, [IVec2::new(0, 0), IVec2::new(1, 1)]);
+    /// assert!(grid.overlaps_at(&piece, IVec2::new(1, 2)));
+    /// assert!(!grid.overlaps_at(&piece, IVec2::new(2, 2)));
+    /// ```
+    pub fn overlaps_at(&self, other: &BitGrid, offset: IVec2) -> bool {
+        (0..other.height).any(|y| {
+            self.target_row(y, offset).is_some_and(|target| {
+                let source = other.row(y);
+                self.row(target)
+                    .iter()
+                    .enumerate()
+                    .any(|(i, word)| word & bits_at(source, i as i64 * 64 - offset.x as i64) != 0)
+            })
+        })
+    }
+
+    /// Returns all positions (set or not) with any set neighbor, including diagonals.
+    pub fn any_neighbors(&self) -> BitGrid {
+        NEIGHBORS
+            .iter()
+            .fold(Self::new(self.width, self.height), |mut grid, &dir| {
+                grid |= &self.shifted(dir);
+                grid
+            })
+    }
+
+    /// Returns all positions (set or not) where the number of set neighbors (including
+    /// diagonals) is in `counts`. The neighbors of all positions are counted at the same
+    /// time with bit operations, like an adder for every bit.
+    ///
+    /// ```
+    /// # use adventofcode::common::bitgrid::BitGrid;
+    /// # use glam::IVec2;
+    /// // one step of Conway's game of life
+    /// let blinker = BitGrid::from_positions(3, 3, (0..3).map(|x| IVec2::new(x, 1)));
+    /// let next = &(&blinker & &blinker.neighbor_counts(2..=3)) | &blinker.neighbor_counts(3..=3);
+    /// assert_eq!(next.to_string(), ".#.\n.#.\n.#.");
+    /// ```
+    pub fn neighbor_counts(&self, counts: RangeInclusive<u8>) -> BitGrid {
+        // binary digits of the count, 8 neighbors need 4 bits
+        let mut digits: [BitGrid; 4] = std::array::from_fn(|_| Self::new(self.width, self.height));
+        for &dir in &NEIGHBORS {
+            let mut carry = self.shifted(dir);
+            for digit in digits.iter_mut() {
+                let next_carry = &*digit & &carry;
+                *digit ^= &carry;
+                carry = next_carry;
+            }
+        }
+
+        let mut result = Self::new(self.width, self.height);
+        for count in counts.filter(|&count| count <= 8) {
+            let matching = digits.iter().enumerate().fold(
+                !&Self::new(self.width, self.height),
+                |matching, (bit, digit)| {
+                    if count >> bit & 1 == 1 {
+                        &matching & digit
+                    } else {
+                        &matching & &!digit
+                    }
+                },
+            );
+            result |= &matching;
+        }
+        result
+    }
+
+    fn index(&self, pos: IVec2) -> (usize, u64) {
+        let (x, y) = (pos.x as usize, pos.y as usize);
+        (y * self.stride + x / 64, 1 << (x % 64))
+    }
+
+    fn target_row(&self, y: usize, offset: IVec2) -> Option<usize> {
+        let target = y as i64 + offset.y as i64;
+        (0..self.height as i64)
+            .contains(&target)
+            .then_some(target as usize)
+    }
+
+    /// Mask for the bits of the last word of every line which are inside the grid.
+    fn last_word_mask(&self) -> u64 {
+        match self.width % 64 {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        }
+    }
+
+    fn combine<F>(&self, other: &BitGrid, combine: F) -> BitGrid
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "Grids need the same size"
+        );
+        let words = self
+            .words
+            .iter()
+            .zip(&other.words)
+            .map(|(&a, &b)| combine(a, b))
+            .collect();
+        Self { words, ..*self }
+    }
+}
+
+/// Returns the 64 bits of a line starting at bit `start`, bits outside of the line are 0.
+fn bits_at(row: &[u64], start: i64) -> u64 {
+    let word = |index: i64| {
+        usize::try_from(index)
+            .ok()
+            .and_then(|index| row.get(index))
+            .copied()
+            .unwrap_or(0)
+    };
+    let (index, bit) = (start.div_euclid(64), start.rem_euclid(64));
+    if bit == 0 {
+        word(index)
+    } else {
+        word(index) >> bit | word(index + 1) << (64 - bit)
+    }
+}
+
+/// Iterator over the indices of the set bits of a word.
+struct BitIter(u64);
+
+impl Iterator for BitIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(bit)
+    }
+}
+
+impl Default for BitGrid {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl BitAnd for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitand(self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitor(self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitGrid {
+    type Output = BitGrid;
+
+    fn bitxor(self, other: &BitGrid) -> BitGrid {
+        self.combine(other, |a, b| a ^ b)
+    }
+}
+
+impl BitAndAssign<&BitGrid> for BitGrid {
+    fn bitand_assign(&mut self, other: &BitGrid) {
+        *self = &*self & other;
+    }
+}
+
+impl BitOrAssign<&BitGrid> for BitGrid {
+    fn bitor_assign(&mut self, other: &BitGrid) {
+        *self = &*self | other;
+    }
+}
+
+impl BitXorAssign<&BitGrid> for BitGrid {
+    fn bitxor_assign(&mut self, other: &BitGrid) {
+        *self = &*self ^ other;
+    }
+}
+
+/// All positions of the grid which aren't set.
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mask = self.last_word_mask();
+        let mut words = self.words.iter().map(|word| !word).collect::<Vec<_>>();
+        if self.stride > 0 {
+            for row in words.chunks_mut(self.stride) {
+                row[self.stride - 1] &= mask;
+            }
+        }
+        BitGrid { words, ..*self }
+    }
+}
+
+/// Draws the grid with `#` for set and `.` for empty positions.
+impl Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                let set = self.contains(IVec2::new(x as i32, y as i32));
+                write!(f, "{}", if set { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// Positions in a wide grid, so lines have several words.
+    fn scattered() -> HashSet<IVec2> {
+        (0..150)
+            .flat_map(|x| (0..7).map(move |y| IVec2::new(x, y)))
+            .filter(|p| (p.x * 7 + p.y * 13) % 5 == 0 || p.x == 63 || p.x == 64)
+            .collect()
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let positions = scattered();
+        let grid = BitGrid::from_positions(150, 7, positions.iter().copied());
+        assert_eq!(grid.len(), positions.len());
+        assert_eq!(grid.iter().collect::<HashSet<_>>(), positions);
+
+        let inverted = !&grid;
+        assert_eq!(inverted.len(), 150 * 7 - positions.len());
+        assert!((&grid & &inverted).is_empty());
+        assert_eq!((&grid | &inverted).len(), 150 * 7);
+        assert_eq!(&grid ^ &grid, BitGrid::new(150, 7));
+    }
+
+    #[test]
+    fn test_shifts_across_words() {
+        let positions = scattered();
+        let grid = BitGrid::from_positions(150, 7, positions.iter().copied());
+        for offset in [
+            IVec2::new(1, 0),
+            IVec2::new(-1, 1),
+            IVec2::new(64, -2),
+            IVec2::new(-70, 3),
+            IVec2::new(200, 0),
+        ] {
+            let expected = positions
+                .iter()
+                .map(|&p| p + offset)
+                .filter(|&p| grid.in_bounds(p))
+                .collect::<HashSet<_>>();
+            assert_eq!(
+                grid.shifted(offset).iter().collect::<HashSet<_>>(),
+                expected,
+                "offset {}",
+                offset
+            );
+        }
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        let positions = scattered();
+        let grid = BitGrid::from_positions(150, 7, positions.iter().copied());
+        for counts in [0..=0, 1..=2, 3..=3, 4..=8] {
+            let expected = (0..150)
+                .flat_map(|x| (0..7).map(move |y| IVec2::new(x, y)))
+                .filter(|&p| {
+                    let count = NEIGHBORS
+                        .iter()
+                        .filter(|&&dir| positions.contains(&(p + dir)))
+                        .count();
+                    counts.contains(&(count as u8))
+                })
+                .collect::<HashSet<_>>();
+            let result = grid.neighbor_counts(counts.clone());
+            assert_eq!(result.iter().collect::<HashSet<_>>(), expected);
+        }
+        assert_eq!(grid.any_neighbors(), grid.neighbor_counts(1..=8));
+    }
+
+    #[test]
+    fn test_resize_expand_and_bounding_box() {
+        let mut grid = BitGrid::from_positions(70, 2, [IVec2::new(65, 0), IVec2::new(3, 1)]);
+        assert_eq!(
+            grid.bounding_box(),
+            Some((IVec2::new(3, 0), IVec2::new(65, 1)))
+        );
+
+        grid.resize(4);
+        assert!(grid.insert(IVec2::new(69, 3)));
+        assert!(!grid.insert(IVec2::new(69, 3)));
+        let expanded = grid.expanded(2);
+        assert_eq!((expanded.width(), expanded.height()), (74, 8));
+        assert_eq!(
+            expanded.bounding_box(),
+            Some((IVec2::new(5, 2), IVec2::new(71, 5)))
+        );
+
+        assert!(grid.remove(IVec2::new(3, 1)));
+        assert!(!grid.remove(IVec2::new(-1, 1)));
+        assert_eq!(BitGrid::new(10, 10).bounding_box(), None);
+    }
+}
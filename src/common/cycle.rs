@@ -0,0 +1,247 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Finds the cycle in the sequence `start, step(start), step(step(start)), ...`
+/// with Floyd's tortoise and hare algorithm: <https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare>
+///
+/// Returns the index of the first state in the cycle and the length of the cycle.
+/// Only a constant number of states is kept in memory, but `step` is called about three times per state.
+///
+/// ```
+/// # use adventofcode::common::cycle::floyd;
+/// // 0, 1, 2, 3, 4, 2, 3, 4, ...
+/// let (start, length) = floyd(&0, |&n| if n == 4 { 2 } else { n + 1 });
+/// assert_eq!((start, length), (2, 3));
+/// ```
+pub fn floyd<S, F>(start: &S, step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = step(start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+
+    let mut cycle_start = 0;
+    tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    (cycle_start, length)
+}
+
+/// Finds the cycle in the sequence `start, step(start), step(step(start)), ...`
+/// with Brent's algorithm: <https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm>
+///
+/// Returns the index of the first state in the cycle and the length of the cycle.
+/// Like [`floyd`] this only keeps a constant number of states, but needs fewer calls to `step`.
+///
+/// ```
+/// # use adventofcode::common::cycle::brent;
+/// // 0, 1, 2, 3, 4, 2, 3, 4, ...
+/// let (start, length) = brent(&0, |&n| if n == 4 { 2 } else { n + 1 });
+/// assert_eq!((start, length), (2, 3));
+/// ```
+pub fn brent<S, F>(start: &S, step: F) -> (usize, usize)
+where
+    S: Clone + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    tortoise = start.clone();
+    hare = start.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        cycle_start += 1;
+    }
+
+    (cycle_start, length)
+}
+
+/// A detected cycle with the metric recorded for every step until the first repetition.
+#[derive(Debug, Clone)]
+pub struct Cycle<M> {
+    /// Step at which the cycle starts.
+    pub start: usize,
+    /// Number of steps in one cycle.
+    pub length: usize,
+    /// Metric after each step, starting with the initial metric at index 0.
+    /// Contains `start + length + 1` entries, so the last entry is the first repetition.
+    pub history: Vec<M>,
+}
+
+impl<M> Cycle<M> {
+    /// Returns the step in the recorded history that is equivalent to step `n`.
+    ///
+    /// ```
+    /// # use adventofcode::common::cycle::Cycle;
+    /// let cycle = Cycle { start: 2, length: 3, history: vec![0, 1, 2, 3, 4, 2] };
+    /// assert_eq!(cycle.equivalent_step(1), 1);
+    /// assert_eq!(cycle.equivalent_step(5), 2);
+    /// assert_eq!(cycle.equivalent_step(1_000_000_000), 4);
+    /// ```
+    pub fn equivalent_step(&self, n: u64) -> usize {
+        if n < self.start as u64 {
+            n as usize
+        } else {
+            self.start + ((n - self.start as u64) % self.length as u64) as usize
+        }
+    }
+
+    /// Returns the metric at step `n`, for metrics that repeat with the cycle.
+    pub fn metric_at(&self, n: u64) -> &M {
+        &self.history[self.equivalent_step(n)]
+    }
+}
+
+impl<M: Copy + Into<i64>> Cycle<M> {
+    /// Returns the metric at step `n`, for metrics that grow by the same amount every cycle
+    /// (like the height of a tower).
+    ///
+    /// ```
+    /// # use adventofcode::common::cycle::Cycle;
+    /// // grows by 10 every cycle of length 2, starting at step 1
+    /// let cycle = Cycle { start: 1, length: 2, history: vec![0, 3, 8, 13] };
+    /// assert_eq!(cycle.extrapolate(3), 13);
+    /// assert_eq!(cycle.extrapolate(4), 18);
+    /// assert_eq!(cycle.extrapolate(5), 23);
+    /// assert_eq!(cycle.extrapolate(1_000_000_001), 5_000_000_003);
+    /// ```
+    pub fn extrapolate(&self, n: u64) -> i64 {
+        if n < self.history.len() as u64 {
+            return self.history[n as usize].into();
+        }
+
+        let growth =
+            self.history[self.start + self.length].into() - self.history[self.start].into();
+        let cycles = (n - self.start as u64) / self.length as u64;
+        self.metric_at(n).to_owned().into() + cycles as i64 * growth
+    }
+}
+
+/// Runs a simulation until a state repeats.
+///
+/// `initial` is the key and metric of the initial state. `step` advances the simulation by
+/// one step and returns the key and metric of the new state. The key must contain everything
+/// that influences the following steps, the metric is what should be recorded (like a height or count).
+///
+/// ```
+/// # use adventofcode::common::cycle::find_cycle;
+/// let mut value = 0;
+/// let cycle = find_cycle((0, 0), || {
+///     value += 3;
+///     (value % 4, value)
+/// });
+/// assert_eq!((cycle.start, cycle.length), (0, 4));
+/// assert_eq!(cycle.history, vec![0, 3, 6, 9, 12]);
+/// assert_eq!(cycle.extrapolate(1000), 3000);
+/// ```
+pub fn find_cycle<K, M, F>(initial: (K, M), mut step: F) -> Cycle<M>
+where
+    K: Eq + Hash,
+    F: FnMut() -> (K, M),
+{
+    let (key, metric) = initial;
+    let mut seen = HashMap::from([(key, 0)]);
+    let mut history = vec![metric];
+
+    loop {
+        let (key, metric) = step();
+        history.push(metric);
+        let current = history.len() - 1;
+
+        if let Some(&start) = seen.get(&key) {
+            return Cycle {
+                start,
+                length: current - start,
+                history,
+            };
+        }
+        seen.insert(key, current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(start: u32, step: impl Fn(&u32) -> u32) -> (usize, usize) {
+        let mut sequence = vec![start];
+        loop {
+            let next = step(sequence.last().unwrap());
+            if let Some(first) = sequence.iter().position(|&s| s == next) {
+                return (first, sequence.len() - first);
+            }
+            sequence.push(next);
+        }
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        for modulus in 1..60 {
+            for start in 0..modulus {
+                let step = |&n: &u32| (n * n + 1) % modulus;
+                let expected = brute_force(start, step);
+
+                assert_eq!(floyd(&start, step), expected);
+                assert_eq!(brent(&start, step), expected);
+
+                let mut current = start;
+                let cycle = find_cycle((start, start), || {
+                    current = step(&current);
+                    (current, current)
+                });
+                assert_eq!((cycle.start, cycle.length), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extrapolate_matches_simulation() {
+        let step = |&(a, b): &(u32, i64)| ((a * 7 + 3) % 23, b + a as i64);
+
+        let mut state = (5, 0);
+        let cycle = find_cycle((state.0, state.1), || {
+            state = step(&state);
+            state
+        });
+
+        let mut state = (5, 0);
+        for n in 0..200 {
+            assert_eq!(cycle.extrapolate(n), state.1);
+            assert_eq!(cycle.metric_at(n), &cycle.history[cycle.equivalent_step(n)]);
+            state = step(&state);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Disjoint sets (union-find) with path compression and union by size:
+//! <https://en.wikipedia.org/wiki/Disjoint-set_data_structure>
+
+use std::{collections::HashMap, hash::Hash};
+
+use itertools::Itertools;
+
+/// Union-find over the elements `0..len`.
+///
+/// ```
+/// # use adventofcode::common::dsu::UnionFind;
+/// let mut sets = UnionFind::new(5);
+/// assert!(sets.union(0, 1));
+/// assert!(sets.union(3, 1));
+/// assert!(!sets.union(0, 3));
+/// assert!(sets.same(0, 3));
+/// assert_eq!(sets.set_size(1), 3);
+/// assert_eq!(sets.count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    count: usize,
+}
+
+impl UnionFind {
+    /// Creates `len` sets with one element each.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            count: len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Adds a new set with one element and returns the element.
+    pub fn push(&mut self) -> usize {
+        let element = self.parents.len();
+        self.parents.push(element);
+        self.sizes.push(1);
+        self.count += 1;
+        element
+    }
+
+    /// Returns the representative of the set containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // point everything on the path directly to the root
+        let mut current = element;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        root
+    }
+
+    /// Merges the sets containing `a` and `b`, returns false if they were already the same set.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Returns the number of elements in the set containing `element`.
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the elements of every set, sets and elements in ascending order.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut sets = HashMap::<usize, Vec<usize>>::new();
+        for element in 0..self.len() {
+            sets.entry(self.find(element)).or_default().push(element);
+        }
+        sets.into_values().sorted().collect()
+    }
+}
+
+/// Union-find over arbitrary values, which are added on first use.
+///
+/// ```
+/// # use adventofcode::common::dsu::DisjointSets;
+/// let mut sets = DisjointSets::new();
+/// sets.union("a", "b");
+/// sets.union("c", "d");
+/// sets.insert("e");
+/// assert!(sets.same(&"a", &"b"));
+/// assert!(!sets.same(&"a", &"c"));
+/// assert_eq!(sets.count(), 3);
+/// assert_eq!(sets.sets(), vec![vec!["a", "b"], vec!["c", "d"], vec!["e"]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DisjointSets<T> {
+    indices: HashMap<T, usize>,
+    values: Vec<T>,
+    sets: UnionFind,
+}
+
+impl<T> Default for DisjointSets<T> {
+    fn default() -> Self {
+        Self {
+            indices: HashMap::new(),
+            values: vec![],
+            sets: UnionFind::new(0),
+        }
+    }
+}
+
+impl<T> DisjointSets<T>
+where
+    T: Clone + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the value as its own set if it's new, returns its index.
+    pub fn insert(&mut self, value: T) -> usize {
+        if let Some(&index) = self.indices.get(&value) {
+            return index;
+        }
+        let index = self.sets.push();
+        self.indices.insert(value.clone(), index);
+        self.values.push(value);
+        index
+    }
+
+    /// Merges the sets containing `a` and `b`, returns false if they were already the same set.
+    pub fn union(&mut self, a: T, b: T) -> bool {
+        let (a, b) = (self.insert(a), self.insert(b));
+        self.sets.union(a, b)
+    }
+
+    /// Returns true if both values were added and are in the same set.
+    pub fn same(&mut self, a: &T, b: &T) -> bool {
+        match (self.indices.get(a), self.indices.get(b)) {
+            (Some(&a), Some(&b)) => self.sets.same(a, b),
+            _ => false,
+        }
+    }
+
+    /// Returns the representative of the set containing the value, if the value was added.
+    pub fn find(&mut self, value: &T) -> Option<&T> {
+        let index = *self.indices.get(value)?;
+        let root = self.sets.find(index);
+        Some(&self.values[root])
+    }
+
+    /// Returns the number of values in the set containing the value (0 if it wasn't added).
+    pub fn set_size(&mut self, value: &T) -> usize {
+        self.indices
+            .get(value)
+            .map_or(0, |&index| self.sets.set_size(index))
+    }
+
+    /// Returns the number of disjoint sets.
+    pub fn count(&self) -> usize {
+        self.sets.count()
+    }
+
+    /// Returns the values of every set, in the order the values were added.
+    pub fn sets(&mut self) -> Vec<Vec<T>> {
+        self.sets
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().map(|i| self.values[i].clone()).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_long_chain() {
+        let len = 100_000;
+        let mut sets = UnionFind::new(len);
+        for i in 1..len {
+            sets.union(i - 1, i);
+        }
+        assert_eq!(sets.count(), 1);
+        assert_eq!(sets.set_size(0), len);
+        assert!(sets.same(0, len - 1));
+    }
+
+    #[test]
+    fn test_sets() {
+        let mut sets = UnionFind::new(6);
+        sets.union(4, 2);
+        sets.union(5, 0);
+        sets.union(2, 5);
+        assert_eq!(sets.sets(), vec![vec![0, 2, 4, 5], vec![1], vec![3]]);
+        assert_eq!(sets.push(), 6);
+        assert_eq!(sets.count(), 4);
+    }
+
+    #[test]
+    fn test_disjoint_sets_unknown_values() {
+        let mut sets = DisjointSets::new();
+        sets.union(1, 2);
+        assert!(!sets.same(&1, &3));
+        assert_eq!(sets.find(&3), None);
+        assert_eq!(sets.set_size(&3), 0);
+        assert_eq!(sets.set_size(&2), 2);
+        assert!(matches!(sets.find(&2), Some(1 | 2)));
+    }
+}
@@ -0,0 +1,538 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use colored::Colorize;
+use glam::IVec2;
+use num::Zero;
+
+use super::grid::print_area;
+
+/// Information recorded during a search, for debugging and visualising the explored states.
+#[derive(Debug, Clone)]
+pub struct Trace<S> {
+    /// All states that were taken from the frontier and expanded.
+    pub explored: HashSet<S>,
+    /// Size of the frontier after each expanded state.
+    pub frontier_sizes: Vec<usize>,
+    /// The returned path, empty if no path was found.
+    pub path: Vec<S>,
+}
+
+impl<S> Default for Trace<S> {
+    fn default() -> Self {
+        Self {
+            explored: HashSet::new(),
+            frontier_sizes: vec![],
+            path: vec![],
+        }
+    }
+}
+
+impl<S: Eq + Hash> Trace<S> {
+    /// Returns the largest size the frontier had during the search.
+    pub fn max_frontier_size(&self) -> usize {
+        self.frontier_sizes
+            .iter()
+            .max()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Prints the explored states and the path on a grid with debug output.
+    ///
+    /// `to_pos` maps a state to its position on the grid, `map_pos` renders a single position.
+    /// Positions on the path are highlighted in red, explored positions in yellow.
+    pub fn print_on_grid<P, F>(&self, area: (IVec2, IVec2), to_pos: P, map_pos: F)
+    where
+        P: Fn(&S) -> IVec2,
+        F: Fn(IVec2) -> String,
+    {
+        let explored = self.explored.iter().map(&to_pos).collect::<HashSet<_>>();
+        let path = self.path.iter().map(&to_pos).collect::<HashSet<_>>();
+
+        print_area(area, |pos| {
+            if path.contains(&pos) {
+                map_pos(pos).red().bold().to_string()
+            } else if explored.contains(&pos) {
+                map_pos(pos).yellow().to_string()
+            } else {
+                map_pos(pos)
+            }
+        });
+    }
+}
+
+/// Breadth-first search from `start` until `success` returns true.
+///
+/// Returns the path including `start` and the target, or `None` if no target is reachable.
+///
+/// ```
+/// # use adventofcode::common::graph::bfs;
+/// let path = bfs(&1, |&n| vec![n + 1, n * 2], |&n| n == 10);
+/// assert_eq!(path, Some(vec![1, 2, 4, 5, 10]));
+/// ```
+pub fn bfs<S, FN, IN, FS>(start: &S, successors: FN, success: FS) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FS: FnMut(&S) -> bool,
+{
+    bfs_internal(start, successors, success, None)
+}
+
+/// Same as [`bfs`], but records the search in `trace`.
+pub fn bfs_traced<S, FN, IN, FS>(
+    start: &S,
+    successors: FN,
+    success: FS,
+    trace: &mut Trace<S>,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FS: FnMut(&S) -> bool,
+{
+    bfs_internal(start, successors, success, Some(trace))
+}
+
+fn bfs_internal<S, FN, IN, FS>(
+    start: &S,
+    mut successors: FN,
+    mut success: FS,
+    mut trace: Option<&mut Trace<S>>,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+    FS: FnMut(&S) -> bool,
+{
+    let mut states = vec![start.clone()];
+    let mut parents = vec![usize::MAX];
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        let node = states[index].clone();
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.explored.insert(node.clone());
+        }
+
+        if success(&node) {
+            let path = reverse_path(&states, &parents, index);
+            if let Some(trace) = trace {
+                trace.path.clone_from(&path);
+            }
+            return Some(path);
+        }
+
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back(states.len());
+                states.push(next);
+                parents.push(index);
+            }
+        }
+
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.frontier_sizes.push(queue.len());
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's shortest path from `start` until `success` returns true.
+///
+/// Returns the path including `start` and the target with the total cost,
+/// or `None` if no target is reachable.
+///
+/// ```
+/// # use adventofcode::common::graph::dijkstra;
+/// let edges = |&n: &u8| match n {
+///     0 => vec![(1, 7), (2, 1)],
+///     2 => vec![(1, 2)],
+///     _ => vec![],
+/// };
+/// assert_eq!(dijkstra(&0, edges, |&n| n == 1), Some((vec![0, 2, 1], 3)));
+/// ```
+pub fn dijkstra<S, C, FN, IN, FS>(start: &S, successors: FN, success: FS) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> bool,
+{
+    astar_internal(start, successors, |_| C::zero(), success, None)
+}
+
+/// Same as [`dijkstra`], but records the search in `trace`.
+pub fn dijkstra_traced<S, C, FN, IN, FS>(
+    start: &S,
+    successors: FN,
+    success: FS,
+    trace: &mut Trace<S>,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FS: FnMut(&S) -> bool,
+{
+    astar_internal(start, successors, |_| C::zero(), success, Some(trace))
+}
+
+/// A* shortest path from `start` until `success` returns true.
+///
+/// The `heuristic` must never overestimate the remaining cost to a target.
+/// Returns the path including `start` and the target with the total cost,
+/// or `None` if no target is reachable.
+///
+/// ```
+/// # use adventofcode::common::graph::astar;
+/// let successors = |&n: &i32| vec![(n - 1, 1), (n + 1, 1)];
+/// let path = astar(&0, successors, |&n| (3 - n).unsigned_abs(), |&n| n == 3);
+/// assert_eq!(path, Some((vec![0, 1, 2, 3], 3)));
+/// ```
+pub fn astar<S, C, FN, IN, FH, FS>(
+    start: &S,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FS: FnMut(&S) -> bool,
+{
+    astar_internal(start, successors, heuristic, success, None)
+}
+
+/// Same as [`astar`], but records the search in `trace`.
+pub fn astar_traced<S, C, FN, IN, FH, FS>(
+    start: &S,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+    trace: &mut Trace<S>,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FS: FnMut(&S) -> bool,
+{
+    astar_internal(start, successors, heuristic, success, Some(trace))
+}
+
+struct Candidate<C> {
+    estimated: C,
+    cost: C,
+    index: usize,
+}
+
+impl<C: Ord> PartialEq for Candidate<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: Ord> Eq for Candidate<C> {}
+
+impl<C: Ord> PartialOrd for Candidate<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: Ord> Ord for Candidate<C> {
+    /// Reversed ordering for the max-heap: lowest estimate first, then the highest cost,
+    /// which prefers candidates that are closer to a target.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimated
+            .cmp(&self.estimated)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+fn astar_internal<S, C, FN, IN, FH, FS>(
+    start: &S,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+    mut trace: Option<&mut Trace<S>>,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FS: FnMut(&S) -> bool,
+{
+    let mut states = vec![start.clone()];
+    let mut parents = vec![usize::MAX];
+    let mut costs = vec![C::zero()];
+    let mut seen = HashMap::from([(start.clone(), 0)]);
+    let mut heap = BinaryHeap::from([Candidate {
+        estimated: heuristic(start),
+        cost: C::zero(),
+        index: 0,
+    }]);
+
+    while let Some(Candidate { cost, index, .. }) = heap.pop() {
+        if cost > costs[index] {
+            continue; // a cheaper way to this node was already found
+        }
+        let node = states[index].clone();
+
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.explored.insert(node.clone());
+        }
+
+        if success(&node) {
+            let path = reverse_path(&states, &parents, index);
+            if let Some(trace) = trace {
+                trace.path.clone_from(&path);
+            }
+            return Some((path, cost));
+        }
+
+        for (next, move_cost) in successors(&node) {
+            let next_cost = cost + move_cost;
+            let next_index = match seen.get(&next) {
+                Some(&next_index) if costs[next_index] <= next_cost => continue,
+                Some(&next_index) => {
+                    parents[next_index] = index;
+                    costs[next_index] = next_cost;
+                    next_index
+                }
+                None => {
+                    seen.insert(next.clone(), states.len());
+                    states.push(next.clone());
+                    parents.push(index);
+                    costs.push(next_cost);
+                    states.len() - 1
+                }
+            };
+            heap.push(Candidate {
+                estimated: next_cost + heuristic(&next),
+                cost: next_cost,
+                index: next_index,
+            });
+        }
+
+        if let Some(trace) = trace.as_deref_mut() {
+            trace.frontier_sizes.push(heap.len());
+        }
+    }
+
+    None
+}
+
+fn reverse_path<S: Clone>(states: &[S], parents: &[usize], mut index: usize) -> Vec<S> {
+    let mut path = vec![];
+    while index != usize::MAX {
+        path.push(states[index].clone());
+        index = parents[index];
+    }
+    path.reverse();
+    path
+}
+
+/// Calculates the distance from `start` to all reachable states with a breadth-first search.
+///
+/// ```
+/// # use adventofcode::common::graph::bfs_distances;
+/// let distances = bfs_distances(&0, |&n: &u8| if n < 3 { vec![n + 1] } else { vec![] });
+/// assert_eq!(distances[&0], 0);
+/// assert_eq!(distances[&3], 3);
+/// ```
+pub fn bfs_distances<S, FN, IN>(start: &S, mut successors: FN) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+
+    while let Some((node, distance)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Calculates the distances between all pairs of different `nodes` in an unweighted graph,
+/// with one breadth-first search per node. Unreachable pairs are missing in the result.
+///
+/// This is faster than [`floyd_warshall`] if only a few nodes of a large graph are relevant.
+///
+/// ```
+/// # use adventofcode::common::graph::all_pairs_distances;
+/// let neighbors = |&n: &u8| [n.checked_sub(1), (n < 9).then_some(n + 1)].into_iter().flatten();
+/// let distances = all_pairs_distances(&[0, 2, 5], neighbors);
+/// assert_eq!(distances[&(0, 5)], 5);
+/// assert_eq!(distances[&(5, 2)], 3);
+/// assert_eq!(distances.len(), 6);
+/// ```
+pub fn all_pairs_distances<S, FN, IN>(nodes: &[S], mut successors: FN) -> HashMap<(S, S), usize>
+where
+    S: Clone + Eq + Hash,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = S>,
+{
+    nodes
+        .iter()
+        .flat_map(|from| {
+            let distances = bfs_distances(from, &mut successors);
+            nodes
+                .iter()
+                .filter(|&to| to != from)
+                .filter_map(|to| {
+                    distances
+                        .get(to)
+                        .map(|&distance| ((from.clone(), to.clone()), distance))
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Calculates the shortest distances between all pairs of nodes `0..size` of a weighted directed graph.
+///
+/// Returns a matrix where `result[from][to]` is the distance, or `None` if `to` isn't reachable.
+///
+/// ```
+/// # use adventofcode::common::graph::floyd_warshall;
+/// let distances = floyd_warshall(3, [(0, 1, 4), (1, 2, 1), (0, 2, 7)]);
+/// assert_eq!(distances[0][2], Some(5));
+/// assert_eq!(distances[2][0], None);
+/// assert_eq!(distances[1][1], Some(0));
+/// ```
+pub fn floyd_warshall<C>(
+    size: usize,
+    edges: impl IntoIterator<Item = (usize, usize, C)>,
+) -> Vec<Vec<Option<C>>>
+where
+    C: Zero + Ord + Copy + Add<Output = C>,
+{
+    let mut distances = vec![vec![None; size]; size];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[i] = Some(C::zero());
+    }
+    for (from, to, cost) in edges {
+        let current = &mut distances[from][to];
+        *current = Some(current.map_or(cost, |c: C| c.min(cost)));
+    }
+
+    for k in 0..size {
+        let via = distances[k].clone();
+        for row in distances.iter_mut() {
+            let Some(to_k) = row[k] else {
+                continue;
+            };
+            for (current, from_k) in row.iter_mut().zip(&via) {
+                if let Some(from_k) = from_k {
+                    let through_k = to_k + *from_k;
+                    if current.is_none_or(|c| through_k < c) {
+                        *current = Some(through_k);
+                    }
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::common::grid::{directions::Directions, distance::ManhattenDistance};
+
+    #[test]
+    fn test_bfs_no_path() {
+        assert_eq!(bfs(&0u8, |&n| (n < 5).then_some(n + 1), |&n| n == 10), None);
+    }
+
+    #[test]
+    fn test_astar_traced_on_grid() {
+        let walls = [IVec2::new(1, 0), IVec2::new(1, 1)];
+        let target = IVec2::new(2, 0);
+        let mut trace = Trace::default();
+
+        let (path, cost) = astar_traced(
+            &IVec2::ZERO,
+            |pos| {
+                pos.directions()
+                    .into_iter()
+                    .filter(|p| p.cmpge(IVec2::ZERO).all() && p.cmple(IVec2::splat(2)).all())
+                    .filter(|p| !walls.contains(p))
+                    .map(|p| (p, 1))
+                    .collect::<Vec<_>>()
+            },
+            |pos| pos.manhattan_distance(&target),
+            |pos| pos == &target,
+            &mut trace,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!(trace.path, path);
+        assert!(path.iter().all(|p| trace.explored.contains(p)));
+        assert!(!trace.explored.iter().any(|p| walls.contains(p)));
+        assert!(trace.max_frontier_size() > 0);
+    }
+
+    #[test]
+    fn test_dijkstra_matches_floyd_warshall() {
+        let edges = [
+            (0, 1, 5),
+            (0, 2, 1),
+            (2, 1, 1),
+            (1, 3, 1),
+            (2, 3, 6),
+            (3, 0, 2),
+        ];
+        let matrix = floyd_warshall(4, edges);
+
+        for (from, row) in matrix.iter().enumerate() {
+            for (to, &expected) in row.iter().enumerate() {
+                let result = dijkstra(
+                    &from,
+                    |&n| {
+                        edges
+                            .iter()
+                            .filter(move |(f, _, _)| *f == n)
+                            .map(|&(_, t, c)| (t, c))
+                    },
+                    |&n| n == to,
+                );
+                assert_eq!(result.map(|(_, cost)| cost), expected);
+            }
+        }
+    }
+}
@@ -9,9 +9,13 @@ pub mod area;
 pub mod directions;
 pub mod distance;
 pub mod minmax;
+pub mod neighbors;
+pub mod polygon;
+pub mod regions;
+pub mod rotation;
 pub mod walk;
 
-pub fn parse_set(lines: impl Iterator<Item = String>) -> HashSet<IVec2> {
+pub fn parse_set<'a>(lines: impl Iterator<Item = &'a str>) -> HashSet<IVec2> {
     lines
         .enumerate()
         .flat_map(move |(y, line)| {
@@ -26,8 +30,8 @@ pub fn parse_set(lines: impl Iterator<Item = String>) -> HashSet<IVec2> {
         .collect()
 }
 
-pub fn parse_map<T>(
-    lines: impl Iterator<Item = String>,
+pub fn parse_map<'a, T>(
+    lines: impl Iterator<Item = &'a str>,
     parse_char: fn(char) -> T,
 ) -> HashMap<IVec2, T> {
     lines
@@ -41,7 +45,10 @@ pub fn parse_map<T>(
         .collect()
 }
 
-pub fn parse_vec<T>(lines: impl Iterator<Item = String>, parse_char: fn(char) -> T) -> Vec<Vec<T>> {
+pub fn parse_vec<'a, T>(
+    lines: impl Iterator<Item = &'a str>,
+    parse_char: fn(char) -> T,
+) -> Vec<Vec<T>> {
     lines
         .map(move |line| line.chars().map(parse_char).collect_vec())
         .collect_vec()
@@ -53,8 +60,8 @@ pub fn print_set(set: &HashSet<IVec2>) {
 }
 pub fn print_set_range(set: &HashSet<IVec2>, range: (IVec2, IVec2)) {
     if output::is_debug_enabled() {
-        for y in range.0.y..=range.1.y {
-            output::println_debug(
+        let lines = (range.0.y..=range.1.y)
+            .map(|y| {
                 (range.0.x..=range.1.x)
                     .map(|x| {
                         if set.contains(&IVec2::new(x, y)) {
@@ -63,9 +70,10 @@ pub fn print_set_range(set: &HashSet<IVec2>, range: (IVec2, IVec2)) {
                             ' '
                         }
                     })
-                    .collect(),
-            );
-        }
+                    .collect()
+            })
+            .collect();
+        output::println_grid(None, lines);
     }
 }
 
@@ -74,21 +82,24 @@ where
     F: Fn(IVec2) -> String,
 {
     if output::is_debug_enabled() {
-        for y in area.0.y..=area.1.y {
-            output::println_debug(
+        let lines = (area.0.y..=area.1.y)
+            .map(|y| {
                 (area.0.x..=area.1.x)
                     .map(|x| map_pos(IVec2::new(x, y)))
-                    .collect(),
-            );
-        }
+                    .collect()
+            })
+            .collect();
+        output::println_grid(None, lines);
     }
 }
 
 pub fn print_grid<T>(grid: &[&[T]], map_to_char: fn(&T) -> char) {
     if output::is_debug_enabled() {
-        for &row in grid {
-            output::println_debug(row.iter().map(map_to_char).collect());
-        }
+        let lines = grid
+            .iter()
+            .map(|row| row.iter().map(map_to_char).collect())
+            .collect();
+        output::println_grid(None, lines);
     }
 }
 
@@ -99,7 +110,7 @@ mod tests {
     #[test]
     fn test_parse_set() {
         let grid = ".#.\n..#\n###";
-        let parsed = parse_set(grid.lines().map(|s| s.to_string()));
+        let parsed = parse_set(grid.lines());
 
         assert_eq!(parsed.len(), 5);
         assert!(parsed.contains(&IVec2::new(1, 0)));
@@ -4,7 +4,8 @@ use itertools::Itertools;
 /// Calculate the area of a polygon defined by the corners of the perimeter.
 /// Using the shoelace formula: <https://en.wikipedia.org/wiki/Shoelace_formula>
 ///
-/// To also add the outer-half of the perimeter line, you need to add `(premiter / 2) + 1` to get the full area.
+/// This is the geometric area, [`super::polygon::Polygon`] also counts the grid positions
+/// inside and on the perimeter.
 pub fn shoelace_area(perimeter: &[IVec2]) -> i64 {
     perimeter
         .iter()
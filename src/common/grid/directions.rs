@@ -1,4 +1,11 @@
-use glam::IVec2;
+use std::{
+    fmt::{self, Display},
+    iter::FusedIterator,
+    str::FromStr,
+};
+
+use glam::{IVec2, IVec3};
+use itertools::Itertools;
 
 pub const UP: IVec2 = IVec2::NEG_Y;
 pub const RIGHT: IVec2 = IVec2::X;
@@ -23,6 +30,44 @@ pub const NEIGHBORS: [IVec2; 8] = [
     LEFT,
 ];
 
+/// The 6 orthogonal directions in 3D.
+pub const DIRECTIONS_3D: [IVec3; 6] = [
+    IVec3::NEG_X,
+    IVec3::X,
+    IVec3::NEG_Y,
+    IVec3::Y,
+    IVec3::NEG_Z,
+    IVec3::Z,
+];
+
+/// All 26 neighbors in 3D, including diagonals, ordered by z, y and x.
+pub const NEIGHBORS_3D: [IVec3; 26] = neighbors_3d();
+
+const fn neighbors_3d() -> [IVec3; 26] {
+    let mut neighbors = [IVec3::ZERO; 26];
+    let mut i = 0;
+    let mut index = 0;
+    while i < 27 {
+        if i != 13 {
+            neighbors[index] = IVec3::new(i % 3 - 1, i / 3 % 3 - 1, i / 9 - 1);
+            index += 1;
+        }
+        i += 1;
+    }
+    neighbors
+}
+
+/// One of the 4 orthogonal directions, clockwise starting with [`Direction::Up`].
+///
+/// ```
+/// # use adventofcode::common::grid::directions::{Direction, Notation};
+/// let dir = "R".parse::<Direction>().unwrap();
+/// assert_eq!(dir, Direction::Right);
+/// assert_eq!(dir.rotate(-90), Direction::Up);
+/// assert_eq!(dir.to_string(), ">");
+/// assert_eq!(dir.symbol(Notation::Compass), 'E');
+/// assert!("x".parse::<Direction>().is_err());
+/// ```
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
@@ -32,18 +77,61 @@ pub enum Direction {
     Left = 3,
 }
 
-impl From<char> for Direction {
-    fn from(c: char) -> Self {
-        match c {
-            '^' | 'N' | 'U' => Direction::Up,
-            '>' | 'E' | 'R' => Direction::Right,
-            'v' | 'S' | 'D' => Direction::Down,
-            '<' | 'W' | 'L' => Direction::Left,
-            _ => panic!("Invalid direction: {}", c),
-        }
-    }
+/// One of the 8 directions including diagonals, clockwise starting with [`Direction8::Up`].
+///
+/// ```
+/// # use adventofcode::common::grid::directions::{Direction8, Notation};
+/// # use glam::IVec2;
+/// let dir = Direction8::try_from(IVec2::new(1, -1)).unwrap();
+/// assert_eq!(dir, Direction8::UpRight);
+/// assert_eq!(dir.rotate(135), Direction8::Down);
+/// assert_eq!(dir.symbol(Notation::Compass), "NE");
+/// assert_eq!("SW".parse::<Direction8>(), Ok(Direction8::DownLeft));
+/// ```
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up = 0,
+    UpRight = 1,
+    Right = 2,
+    DownRight = 3,
+    Down = 4,
+    DownLeft = 5,
+    Left = 6,
+    UpLeft = 7,
+}
+
+/// How directions are written, all notations can be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    /// `^`, `>`, `v` and `<`, diagonals are written with [`Notation::Unicode`] arrows
+    Arrows,
+    /// `N`, `NE`, `E`, ...
+    Compass,
+    /// `U`, `UR`, `R`, ...
+    Letters,
+    /// `↑`, `↗`, `→`, ...
+    Unicode,
 }
 
+const NOTATIONS: [Notation; 4] = [
+    Notation::Arrows,
+    Notation::Compass,
+    Notation::Letters,
+    Notation::Unicode,
+];
+
+const DIRECTIONS_8: [IVec2; 8] = [
+    UP,
+    TOP_RIGHT,
+    RIGHT,
+    BOTTOM_RIGHT,
+    DOWN,
+    BOTTOM_LEFT,
+    LEFT,
+    TOP_LEFT,
+];
+
 #[derive(Debug)]
 pub enum Turn {
     Left,
@@ -51,31 +139,192 @@ pub enum Turn {
     Flip,
 }
 
+impl Turn {
+    /// Clockwise rotation of the turn.
+    pub fn degrees(&self) -> i32 {
+        match self {
+            Turn::Left => -90,
+            Turn::Right => 90,
+            Turn::Flip => 180,
+        }
+    }
+}
+
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
     pub fn turn(&self, turn: &Turn) -> Self {
-        match turn {
-            Turn::Left => match self {
-                Direction::Up => Direction::Left,
-                Direction::Right => Direction::Up,
-                Direction::Down => Direction::Right,
-                Direction::Left => Direction::Down,
-            },
-            Turn::Right => match self {
-                Direction::Up => Direction::Right,
-                Direction::Right => Direction::Down,
-                Direction::Down => Direction::Left,
-                Direction::Left => Direction::Up,
-            },
-            Turn::Flip => match self {
-                Direction::Up => Direction::Down,
-                Direction::Right => Direction::Left,
-                Direction::Down => Direction::Up,
-                Direction::Left => Direction::Right,
-            },
+        self.rotate(turn.degrees())
+    }
+
+    /// Rotates clockwise (or counterclockwise for negative degrees) by a multiple of 90 degrees.
+    pub fn rotate(&self, degrees: i32) -> Self {
+        assert_eq!(degrees % 90, 0, "Invalid rotation by {} degrees", degrees);
+        Self::ALL[(*self as i32 + degrees / 90).rem_euclid(4) as usize]
+    }
+
+    pub fn to_ivec2(self) -> IVec2 {
+        DIRECTIONS[self as usize]
+    }
+
+    pub fn symbol(&self, notation: Notation) -> char {
+        let symbols = match notation {
+            Notation::Arrows => ['^', '>', 'v', '<'],
+            Notation::Compass => ['N', 'E', 'S', 'W'],
+            Notation::Letters => ['U', 'R', 'D', 'L'],
+            Notation::Unicode => ['↑', '→', '↓', '←'],
+        };
+        symbols[*self as usize]
+    }
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    /// Rotates clockwise (or counterclockwise for negative degrees) by a multiple of 45 degrees.
+    pub fn rotate(&self, degrees: i32) -> Self {
+        assert_eq!(degrees % 45, 0, "Invalid rotation by {} degrees", degrees);
+        Self::ALL[(*self as i32 + degrees / 45).rem_euclid(8) as usize]
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        *self as u8 % 2 == 1
+    }
+
+    pub fn to_ivec2(self) -> IVec2 {
+        DIRECTIONS_8[self as usize]
+    }
+
+    pub fn symbol(&self, notation: Notation) -> &'static str {
+        let symbols = match notation {
+            Notation::Arrows => ["^", "↗", ">", "↘", "v", "↙", "<", "↖"],
+            Notation::Compass => ["N", "NE", "E", "SE", "S", "SW", "W", "NW"],
+            Notation::Letters => ["U", "UR", "R", "DR", "D", "DL", "L", "UL"],
+            Notation::Unicode => ["↑", "↗", "→", "↘", "↓", "↙", "←", "↖"],
+        };
+        symbols[*self as usize]
+    }
+}
+
+impl From<Direction> for IVec2 {
+    fn from(dir: Direction) -> Self {
+        dir.to_ivec2()
+    }
+}
+
+impl From<Direction8> for IVec2 {
+    fn from(dir: Direction8) -> Self {
+        dir.to_ivec2()
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        Direction8::ALL[dir as usize * 2]
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = String;
+
+    fn try_from(dir: Direction8) -> Result<Self, String> {
+        if dir.is_diagonal() {
+            Err(format!("Diagonal direction {:?} isn't orthogonal", dir))
+        } else {
+            Ok(Direction::ALL[dir as usize / 2])
+        }
+    }
+}
+
+impl TryFrom<IVec2> for Direction {
+    type Error = String;
+
+    fn try_from(vec: IVec2) -> Result<Self, String> {
+        DIRECTIONS
+            .iter()
+            .position(|&dir| dir == vec)
+            .map(|i| Direction::ALL[i])
+            .ok_or_else(|| format!("Invalid direction: {}", vec))
+    }
+}
+
+impl TryFrom<IVec2> for Direction8 {
+    type Error = String;
+
+    fn try_from(vec: IVec2) -> Result<Self, String> {
+        DIRECTIONS_8
+            .iter()
+            .position(|&dir| dir == vec)
+            .map(|i| Direction8::ALL[i])
+            .ok_or_else(|| format!("Invalid direction: {}", vec))
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, String> {
+        NOTATIONS
+            .iter()
+            .find_map(|&notation| Direction::ALL.into_iter().find(|d| d.symbol(notation) == c))
+            .ok_or_else(|| format!("Invalid direction: {}", c))
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.chars().exactly_one() {
+            Ok(c) => Direction::try_from(c),
+            Err(_) => Err(format!("Invalid direction: {}", s)),
         }
     }
 }
 
+impl FromStr for Direction8 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        NOTATIONS
+            .iter()
+            .find_map(|&notation| {
+                Direction8::ALL
+                    .into_iter()
+                    .find(|d| d.symbol(notation) == s)
+            })
+            .ok_or_else(|| format!("Invalid direction: {}", s))
+    }
+}
+
+/// Writes the direction as an arrow, see [`Notation::Arrows`].
+impl Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol(Notation::Arrows))
+    }
+}
+
+/// Writes the direction as an arrow, see [`Notation::Unicode`].
+impl Display for Direction8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol(Notation::Unicode))
+    }
+}
+
 pub trait Directions {
     fn up(&self) -> Self;
     fn right(&self) -> Self;
@@ -94,7 +343,11 @@ pub trait Directions {
     where
         Self: Sized;
 
-    fn line_to(&self, other: &Self) -> Box<dyn Iterator<Item = Self> + '_>
+    fn line_to(&self, other: &Self) -> Line
+    where
+        Self: Sized;
+
+    fn line_until(&self, other: &Self) -> Line
     where
         Self: Sized;
 }
@@ -129,7 +382,7 @@ impl Directions for IVec2 {
         IVec2::new(x as i32, y as i32)
     }
 
-    /// Returns an iterator over the line between `self` and `other`.
+    /// Returns an iterator over the line between `self` and `other`, see [`Line`].
     /// The iterator includes `self` and `other`.
     ///
     /// ```
@@ -141,10 +394,238 @@ impl Directions for IVec2 {
     /// let line = start.line_to(&end).collect::<Vec<_>>();
     /// assert_eq!(line, vec![IVec2::new(2, 0), IVec2::new(3, 1), IVec2::new(4, 2), IVec2::new(5, 3)]);
     /// ```
-    fn line_to(&self, other: &Self) -> Box<dyn Iterator<Item = Self> + '_> {
-        let direction = self.direction(other);
-        let diff = *other - *self;
-        let distance = diff.x.max(diff.y).abs();
-        Box::new((0..=distance).map(move |i| *self + direction * i))
+    fn line_to(&self, other: &Self) -> Line {
+        Line::new(*self, *other)
+    }
+
+    /// Returns an iterator over the line between `self` and `other`, without `other`.
+    ///
+    /// ```
+    /// # use glam::IVec2;
+    /// # use adventofcode::common::grid::directions::Directions;
+    /// #
+    /// let start = IVec2::new(5, 0);
+    /// let line = start.line_until(&IVec2::ZERO).collect::<Vec<_>>();
+    /// assert_eq!(line.len(), 5);
+    /// assert_eq!(line.last(), Some(&IVec2::new(1, 0)));
+    /// ```
+    fn line_until(&self, other: &Self) -> Line {
+        Line::exclusive(*self, *other)
+    }
+}
+
+/// Iterator over the positions on a line with Bresenham's algorithm:
+/// <https://en.wikipedia.org/wiki/Bresenham%27s_line_algorithm>
+///
+/// Works for any slope, consecutive positions are neighbors (including diagonals).
+/// Horizontal, vertical and 45° lines contain exactly the positions on the line.
+///
+/// ```
+/// # use glam::IVec2;
+/// # use adventofcode::common::grid::directions::Line;
+/// let line = Line::new(IVec2::new(0, 0), IVec2::new(4, -2)).collect::<Vec<_>>();
+/// assert_eq!(
+///     line,
+///     vec![
+///         IVec2::new(0, 0),
+///         IVec2::new(1, -1),
+///         IVec2::new(2, -1),
+///         IVec2::new(3, -2),
+///         IVec2::new(4, -2),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Line {
+    position: IVec2,
+    /// Absolute distance on both axes, with y negated
+    delta: IVec2,
+    step: IVec2,
+    error: i32,
+    remaining: usize,
+}
+
+impl Line {
+    /// Creates a line from `start` to `end`, including both.
+    pub fn new(start: IVec2, end: IVec2) -> Self {
+        let diff = end - start;
+        Self {
+            position: start,
+            delta: IVec2::new(diff.x.abs(), -diff.y.abs()),
+            step: diff.signum(),
+            error: diff.x.abs() - diff.y.abs(),
+            remaining: diff.abs().max_element() as usize + 1,
+        }
+    }
+
+    /// Creates a line from `start` to `end`, without `end`.
+    pub fn exclusive(start: IVec2, end: IVec2) -> Self {
+        let mut line = Self::new(start, end);
+        line.remaining -= 1;
+        line
+    }
+}
+
+impl Iterator for Line {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let position = self.position;
+        let error = 2 * self.error;
+        if error >= self.delta.y {
+            self.error += self.delta.y;
+            self.position.x += self.step.x;
+        }
+        if error <= self.delta.x {
+            self.error += self.delta.x;
+            self.position.y += self.step.y;
+        }
+        Some(position)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+
+impl FusedIterator for Line {}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_straight_lines() {
+        let start = IVec2::new(5, 0);
+        assert_eq!(
+            start.line_to(&IVec2::ZERO).collect_vec(),
+            (0..=5).rev().map(|x| IVec2::new(x, 0)).collect_vec()
+        );
+        assert_eq!(start.line_to(&start).collect_vec(), vec![start]);
+        assert_eq!(start.line_until(&start).count(), 0);
+    }
+
+    #[test]
+    fn test_rotations() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.rotate(360), dir);
+            assert_eq!(dir.turn(&Turn::Left).turn(&Turn::Right), dir);
+            assert_eq!(dir.turn(&Turn::Flip).to_ivec2(), -dir.to_ivec2());
+            assert_eq!(
+                Direction8::from(dir).rotate(90),
+                Direction8::from(dir.rotate(90))
+            );
+        }
+        for dir in Direction8::ALL {
+            assert_eq!(dir.rotate(-45).rotate(45), dir);
+            assert_eq!(dir.rotate(180).to_ivec2(), -dir.to_ivec2());
+            assert_eq!(dir.rotate(90).to_ivec2(), dir.to_ivec2().perp());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid rotation by 45 degrees")]
+    fn test_invalid_rotation() {
+        Direction::Up.rotate(45);
+    }
+
+    #[test]
+    fn test_conversions() {
+        for dir in Direction::ALL {
+            assert_eq!(Direction::try_from(dir.to_ivec2()), Ok(dir));
+            assert_eq!(Direction::try_from(Direction8::from(dir)), Ok(dir));
+        }
+        for dir in Direction8::ALL {
+            assert_eq!(Direction8::try_from(IVec2::from(dir)), Ok(dir));
+        }
+        assert!(Direction::try_from(Direction8::DownLeft).is_err());
+        assert_eq!(
+            Direction::try_from(IVec2::new(1, 1)),
+            Err("Invalid direction: [1, 1]".to_string())
+        );
+        assert_eq!(
+            DIRECTIONS_8
+                .into_iter()
+                .sorted_by_key(|d| (d.y, d.x))
+                .collect_vec(),
+            NEIGHBORS
+                .into_iter()
+                .sorted_by_key(|d| (d.y, d.x))
+                .collect_vec()
+        );
+    }
+
+    #[test]
+    fn test_parse_notations() {
+        for notation in NOTATIONS {
+            for dir in Direction::ALL {
+                let symbol = dir.symbol(notation).to_string();
+                assert_eq!(symbol.parse::<Direction>(), Ok(dir));
+            }
+            for dir in Direction8::ALL {
+                assert_eq!(dir.symbol(notation).parse::<Direction8>(), Ok(dir));
+            }
+        }
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert_eq!(
+            "UP".parse::<Direction>(),
+            Err("Invalid direction: UP".to_string())
+        );
+        assert_eq!(Direction8::UpLeft.to_string(), "↖");
+    }
+
+    fn ivec2() -> impl Strategy<Value = IVec2> {
+        (-50..50, -50..50).prop_map(|(x, y)| IVec2::new(x, y))
+    }
+
+    proptest! {
+        #[test]
+        fn line_connects_start_and_end(start in ivec2(), end in ivec2()) {
+            let line = start.line_to(&end).collect_vec();
+            prop_assert_eq!(line.len(), (end - start).abs().max_element() as usize + 1);
+            prop_assert_eq!(line.len(), start.line_to(&end).len());
+            prop_assert_eq!(line.first(), Some(&start));
+            prop_assert_eq!(line.last(), Some(&end));
+            prop_assert!(line
+                .iter()
+                .tuple_windows()
+                .all(|(a, b)| (*b - *a).abs().max_element() == 1));
+        }
+
+        #[test]
+        fn line_stays_close(start in ivec2(), end in ivec2()) {
+            // at most half a step away from the exact line on the shorter axis
+            let diff = end - start;
+            for position in start.line_to(&end) {
+                let offset = (position - start).perp_dot(diff).abs();
+                prop_assert!(2 * offset <= diff.abs().max_element(), "{}", position);
+            }
+        }
+
+        #[test]
+        fn straight_lines_are_exact(start in ivec2(), direction in 0..8usize, distance in 0..50) {
+            let step = NEIGHBORS[direction];
+            let end = start + step * distance;
+            prop_assert!(start
+                .line_to(&end)
+                .zip_eq(0..=distance)
+                .all(|(position, i)| position == start + step * i));
+        }
+
+        #[test]
+        fn exclusive_line_skips_end(start in ivec2(), end in ivec2()) {
+            let mut line = start.line_to(&end).collect_vec();
+            line.pop();
+            prop_assert_eq!(start.line_until(&end).collect_vec(), line);
+        }
     }
 }
@@ -1,4 +1,4 @@
-use glam::IVec2;
+use glam::{IVec2, IVec3};
 
 pub trait ManhattenDistance {
     fn manhattan_distance(&self, other: &Self) -> u32;
@@ -9,3 +9,11 @@ impl ManhattenDistance for IVec2 {
         (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
     }
 }
+
+impl ManhattenDistance for IVec3 {
+    fn manhattan_distance(&self, other: &IVec3) -> u32 {
+        (self.x - other.x).unsigned_abs()
+            + (self.y - other.y).unsigned_abs()
+            + (self.z - other.z).unsigned_abs()
+    }
+}
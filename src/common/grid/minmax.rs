@@ -1,4 +1,4 @@
-use glam::IVec2;
+use glam::{IVec2, IVec3};
 
 pub fn minmax_ivec2<'a>(vecs: impl Iterator<Item = &'a IVec2>) -> (IVec2, IVec2) {
     let mut min = IVec2::new(i32::MAX, i32::MAX);
@@ -12,6 +12,59 @@ pub fn minmax_ivec2<'a>(vecs: impl Iterator<Item = &'a IVec2>) -> (IVec2, IVec2)
     (min, max)
 }
 
+pub fn minmax_ivec3<'a>(vecs: impl Iterator<Item = &'a IVec3>) -> (IVec3, IVec3) {
+    let mut min = IVec3::splat(i32::MAX);
+    let mut max = IVec3::splat(i32::MIN);
+
+    for vec in vecs {
+        min = min.min(*vec);
+        max = max.max(*vec);
+    }
+
+    (min, max)
+}
+
+/// Bounding boxes (with inclusive corners) of 2D and 3D positions.
+///
+/// ```
+/// # use adventofcode::common::grid::minmax::BoundingBox;
+/// # use glam::IVec3;
+/// let cubes = [IVec3::new(1, 2, 3), IVec3::new(-1, 5, 0)];
+/// let bounds = IVec3::bounding_box(cubes.iter());
+/// assert_eq!(bounds, (IVec3::new(-1, 2, 0), IVec3::new(1, 5, 3)));
+/// assert!(IVec3::new(0, 3, 3).in_bounds(bounds));
+/// assert!(!IVec3::new(0, 3, 4).in_bounds(bounds));
+/// ```
+pub trait BoundingBox: Sized + Copy {
+    /// Returns the smallest and largest coordinates of the positions.
+    fn bounding_box<'a>(vecs: impl Iterator<Item = &'a Self>) -> (Self, Self)
+    where
+        Self: 'a;
+
+    /// Returns true if the position is inside the bounds (inclusive).
+    fn in_bounds(&self, bounds: (Self, Self)) -> bool;
+}
+
+impl BoundingBox for IVec2 {
+    fn bounding_box<'a>(vecs: impl Iterator<Item = &'a Self>) -> (Self, Self) {
+        minmax_ivec2(vecs)
+    }
+
+    fn in_bounds(&self, bounds: (Self, Self)) -> bool {
+        self.cmpge(bounds.0).all() && self.cmple(bounds.1).all()
+    }
+}
+
+impl BoundingBox for IVec3 {
+    fn bounding_box<'a>(vecs: impl Iterator<Item = &'a Self>) -> (Self, Self) {
+        minmax_ivec3(vecs)
+    }
+
+    fn in_bounds(&self, bounds: (Self, Self)) -> bool {
+        self.cmpge(bounds.0).all() && self.cmple(bounds.1).all()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
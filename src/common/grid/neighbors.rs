@@ -0,0 +1,124 @@
+//! Neighbors of 2D and 3D positions with the same API, so searches work in both.
+
+use std::{collections::HashSet, hash::Hash, ops::Add};
+
+use glam::{IVec2, IVec3};
+
+use super::{
+    directions::{DIRECTIONS, DIRECTIONS_3D, NEIGHBORS, NEIGHBORS_3D},
+    minmax::BoundingBox,
+};
+use crate::common::graph::bfs_distances;
+
+/// Orthogonal and diagonal neighbors of a position.
+///
+/// ```
+/// # use adventofcode::common::grid::neighbors::Neighbors;
+/// # use glam::{IVec2, IVec3};
+/// assert_eq!(IVec2::ZERO.orthogonal_neighbors().count(), 4);
+/// assert_eq!(IVec2::ZERO.all_neighbors().count(), 8);
+/// assert_eq!(IVec3::ZERO.orthogonal_neighbors().count(), 6);
+/// assert_eq!(IVec3::ZERO.all_neighbors().count(), 26);
+/// assert!(IVec3::ONE.all_neighbors().any(|n| n == IVec3::new(2, 0, 2)));
+/// ```
+pub trait Neighbors: Copy + Eq + Hash + Add<Output = Self> + 'static {
+    /// Offsets to the orthogonal neighbors (4 in 2D, 6 in 3D).
+    const ORTHOGONAL: &'static [Self];
+    /// Offsets to all neighbors including diagonals (8 in 2D, 26 in 3D).
+    const ALL: &'static [Self];
+
+    fn orthogonal_neighbors(self) -> impl Iterator<Item = Self> {
+        Self::ORTHOGONAL.iter().map(move |&offset| self + offset)
+    }
+
+    fn all_neighbors(self) -> impl Iterator<Item = Self> {
+        Self::ALL.iter().map(move |&offset| self + offset)
+    }
+}
+
+impl Neighbors for IVec2 {
+    const ORTHOGONAL: &'static [Self] = &DIRECTIONS;
+    const ALL: &'static [Self] = &NEIGHBORS;
+}
+
+impl Neighbors for IVec3 {
+    const ORTHOGONAL: &'static [Self] = &DIRECTIONS_3D;
+    const ALL: &'static [Self] = &NEIGHBORS_3D;
+}
+
+/// Returns all positions that can be reached from `start` through orthogonal neighbors
+/// within `bounds` (inclusive) where `is_open` returns true, including `start`.
+///
+/// ```
+/// # use adventofcode::common::grid::neighbors::flood_fill;
+/// # use glam::IVec3;
+/// // a wall at x = 1 separates the cube
+/// let filled = flood_fill(IVec3::ZERO, (IVec3::ZERO, IVec3::splat(2)), |p| p.x != 1);
+/// assert_eq!(filled.len(), 9);
+/// ```
+pub fn flood_fill<V, F>(start: V, bounds: (V, V), is_open: F) -> HashSet<V>
+where
+    V: Neighbors + BoundingBox,
+    F: Fn(&V) -> bool,
+{
+    bfs_distances(&start, |&pos| {
+        pos.orthogonal_neighbors()
+            .filter(|next| next.in_bounds(bounds) && is_open(next))
+            .collect::<Vec<_>>()
+    })
+    .into_keys()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbors_are_unique_and_adjacent() {
+        let neighbors = IVec3::ZERO.all_neighbors().collect::<HashSet<_>>();
+        assert_eq!(neighbors.len(), 26);
+        assert!(neighbors
+            .iter()
+            .all(|n| n.abs().max_element() == 1 && *n != IVec3::ZERO));
+
+        let orthogonal = IVec3::ZERO.orthogonal_neighbors().collect::<HashSet<_>>();
+        assert!(orthogonal.is_subset(&neighbors));
+        assert!(orthogonal.iter().all(|n| n.abs().element_sum() == 1));
+    }
+
+    #[test]
+    fn test_flood_fill_exterior_surface() {
+        // a hollow 3x3x3 cube: the exterior surface is that of a solid cube
+        let cubes = (0..27)
+            .map(|i| IVec3::new(i % 3, i / 3 % 3, i / 9))
+            .filter(|&p| p != IVec3::ONE)
+            .collect::<HashSet<_>>();
+        let (min, max) = IVec3::bounding_box(cubes.iter());
+        let outside = flood_fill(min - 1, (min - 1, max + 1), |p| !cubes.contains(p));
+
+        let exterior_surface = cubes
+            .iter()
+            .flat_map(|cube| cube.orthogonal_neighbors())
+            .filter(|n| outside.contains(n))
+            .count();
+        let total_surface = cubes
+            .iter()
+            .flat_map(|cube| cube.orthogonal_neighbors())
+            .filter(|n| !cubes.contains(n))
+            .count();
+
+        assert_eq!(exterior_surface, 6 * 9);
+        assert_eq!(total_surface, 6 * 9 + 6);
+        assert!(!outside.contains(&IVec3::ONE));
+    }
+
+    #[test]
+    fn test_flood_fill_2d() {
+        let walls = [IVec2::new(1, 0), IVec2::new(1, 1), IVec2::new(0, 1)];
+        let filled = flood_fill(IVec2::ZERO, (IVec2::ZERO, IVec2::splat(2)), |p| {
+            !walls.contains(p)
+        });
+        assert_eq!(filled, HashSet::from([IVec2::ZERO]));
+    }
+}
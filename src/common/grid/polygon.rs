@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+
+use glam::IVec2;
+use itertools::Itertools;
+use num::integer::gcd;
+
+use super::{area::shoelace_area, directions::Direction, minmax::minmax_ivec2, walk::Walk};
+
+/// A closed polygon with corners on the grid, like a loop dug or walked through a grid.
+///
+/// Lattice points are the grid positions, so the "area" of a loop in a grid puzzle is
+/// usually [`Polygon::lattice_points`] (including the loop) or [`Polygon::interior_points`]
+/// (only the enclosed positions), not the geometric [`Polygon::area`].
+///
+/// ```
+/// # use adventofcode::common::grid::{directions::Direction, polygon::Polygon};
+/// # use glam::IVec2;
+/// // a 3x3 square of positions
+/// use Direction::*;
+/// let square = Polygon::from_moves([(Right, 2), (Down, 2), (Left, 2), (Up, 2)]);
+/// assert_eq!(square.area(), 4);
+/// assert_eq!(square.perimeter(), 8);
+/// assert_eq!(square.interior_points(), 1);
+/// assert_eq!(square.lattice_points(), 9);
+/// assert!(square.contains(IVec2::new(1, 1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<IVec2>,
+}
+
+impl Polygon {
+    /// Creates a polygon from its corners, the last corner connects to the first.
+    pub fn new(vertices: Vec<IVec2>) -> Self {
+        Self { vertices }
+    }
+
+    /// Creates a polygon by walking the moves from the origin, the walk should end at the origin.
+    pub fn from_moves(moves: impl IntoIterator<Item = (Direction, i32)>) -> Self {
+        let mut position = IVec2::ZERO;
+        let vertices = moves
+            .into_iter()
+            .map(|(dir, distance)| {
+                position = position.move_distance(dir, distance);
+                position
+            })
+            .collect();
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[IVec2] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (IVec2, IVec2)> + '_ {
+        self.vertices.iter().copied().circular_tuple_windows()
+    }
+
+    /// Geometric area of the polygon with the shoelace formula.
+    pub fn area(&self) -> i64 {
+        shoelace_area(&self.vertices)
+    }
+
+    /// Length of the outline, for horizontal and vertical edges (diagonals count their steps).
+    pub fn perimeter(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| (b - a).abs().max_element() as i64)
+            .sum()
+    }
+
+    /// Number of lattice points on the outline.
+    pub fn boundary_points(&self) -> i64 {
+        self.edges()
+            .map(|(a, b)| {
+                let diff = (b - a).abs();
+                gcd(diff.x, diff.y) as i64
+            })
+            .sum()
+    }
+
+    /// Number of lattice points strictly inside, with Pick's theorem:
+    /// <https://en.wikipedia.org/wiki/Pick%27s_theorem>
+    pub fn interior_points(&self) -> i64 {
+        self.area() - self.boundary_points() / 2 + 1
+    }
+
+    /// Number of lattice points inside or on the outline.
+    pub fn lattice_points(&self) -> i64 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Returns true if the point is on the outline.
+    pub fn is_on_boundary(&self, point: IVec2) -> bool {
+        self.edges().any(|(a, b)| {
+            let (ab, ap) = ((b - a).as_i64vec2(), (point - a).as_i64vec2());
+            ab.perp_dot(ap) == 0 && point.cmpge(a.min(b)).all() && point.cmple(a.max(b)).all()
+        })
+    }
+
+    /// Returns true if the point is inside the polygon or on the outline.
+    pub fn contains(&self, point: IVec2) -> bool {
+        if self.is_on_boundary(point) {
+            return true;
+        }
+
+        // count the edges crossed by a ray from the point to the right
+        self.edges()
+            .filter(|&(a, b)| (a.y > point.y) != (b.y > point.y))
+            .filter(|&(a, b)| {
+                let dy = (b.y - a.y) as i64;
+                let lhs = (point.x - a.x) as i64 * dy;
+                let rhs = (point.y - a.y) as i64 * (b.x - a.x) as i64;
+                if dy > 0 {
+                    lhs < rhs
+                } else {
+                    lhs > rhs
+                }
+            })
+            .count()
+            % 2
+            == 1
+    }
+
+    /// Returns all lattice points inside the polygon or on the outline.
+    ///
+    /// ```
+    /// # use adventofcode::common::grid::polygon::Polygon;
+    /// # use glam::IVec2;
+    /// let triangle = Polygon::new(vec![IVec2::new(0, 0), IVec2::new(4, 0), IVec2::new(0, 4)]);
+    /// let points = triangle.rasterize();
+    /// assert_eq!(points.len() as i64, triangle.lattice_points());
+    /// assert!(points.contains(&IVec2::new(2, 2)));
+    /// assert!(!points.contains(&IVec2::new(3, 2)));
+    /// ```
+    pub fn rasterize(&self) -> HashSet<IVec2> {
+        if self.vertices.is_empty() {
+            return HashSet::new();
+        }
+        let (min, max) = minmax_ivec2(self.vertices.iter());
+
+        let mut points = self.boundary().collect::<HashSet<_>>();
+        for y in min.y..=max.y {
+            // x positions where the row crosses the outline, inside is between pairs of crossings
+            let crossings = self
+                .edges()
+                .filter(|&(a, b)| (a.y > y) != (b.y > y))
+                .map(|(a, b)| {
+                    a.x as f64 + (y - a.y) as f64 * (b.x - a.x) as f64 / (b.y - a.y) as f64
+                })
+                .sorted_by(f64::total_cmp)
+                .collect_vec();
+            for (start, end) in crossings.into_iter().tuples() {
+                points.extend((start.ceil() as i32..=end.floor() as i32).map(|x| IVec2::new(x, y)));
+            }
+        }
+        points
+    }
+
+    /// Returns the lattice points on the outline.
+    fn boundary(&self) -> impl Iterator<Item = IVec2> + '_ {
+        self.edges().flat_map(|(a, b)| {
+            let diff = b - a;
+            let steps = gcd(diff.x, diff.y).max(1);
+            let step = diff / steps;
+            (0..steps).map(move |i| a + step * i)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Direction::*;
+
+    /// The loop of the 2023 day 10 example with 4 enclosed tiles.
+    fn pipe_loop() -> Polygon {
+        Polygon::from_moves([
+            (Right, 8),
+            (Down, 6),
+            (Left, 3),
+            (Up, 2),
+            (Right, 2),
+            (Up, 3),
+            (Left, 6),
+            (Down, 3),
+            (Right, 2),
+            (Down, 2),
+            (Left, 3),
+            (Up, 6),
+        ])
+    }
+
+    #[test]
+    fn test_pipe_loop() {
+        let polygon = pipe_loop();
+        assert_eq!(polygon.perimeter(), 46);
+        assert_eq!(polygon.boundary_points(), 46);
+        assert_eq!(polygon.interior_points(), 4);
+
+        let points = polygon.rasterize();
+        assert_eq!(points.len() as i64, polygon.lattice_points());
+        // the gap between the two legs of the loop is outside
+        assert!(!polygon.contains(IVec2::new(4, 5)));
+        assert!(!points.contains(&IVec2::new(4, 5)));
+        assert!(polygon.contains(IVec2::new(1, 5)));
+        assert!(polygon.is_on_boundary(IVec2::new(3, 4)));
+    }
+
+    #[test]
+    fn test_rasterize_matches_contains() {
+        let polygon = Polygon::new(vec![
+            IVec2::new(0, 0),
+            IVec2::new(6, 2),
+            IVec2::new(3, 3),
+            IVec2::new(5, 7),
+            IVec2::new(-2, 4),
+        ]);
+        let points = polygon.rasterize();
+        assert_eq!(points.len() as i64, polygon.lattice_points());
+        for x in -3..8 {
+            for y in -1..9 {
+                let point = IVec2::new(x, y);
+                assert_eq!(
+                    points.contains(&point),
+                    polygon.contains(point),
+                    "{}",
+                    point
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_counterclockwise() {
+        let square = Polygon::from_moves([(Up, 4), (Left, 4), (Down, 4), (Right, 4)]);
+        assert_eq!(square.area(), 16);
+        assert_eq!(square.interior_points(), 9);
+        assert_eq!(square.lattice_points(), 25);
+    }
+}
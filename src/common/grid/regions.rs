@@ -0,0 +1,197 @@
+//! Connected regions of equal values in a grid, like garden plots or flooded areas.
+
+use std::collections::{HashMap, HashSet};
+
+use glam::IVec2;
+use itertools::Itertools;
+
+use super::directions::{DIRECTIONS, DOWN, RIGHT};
+use crate::common::dsu::UnionFind;
+
+/// Positions with the same value which are connected through orthogonal neighbors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<T> {
+    pub value: T,
+    pub positions: HashSet<IVec2>,
+}
+
+impl<T> Region<T> {
+    pub fn area(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Number of edges between the region and other positions (or the outside of the grid).
+    pub fn perimeter(&self) -> usize {
+        self.positions
+            .iter()
+            .flat_map(|&pos| DIRECTIONS.map(|dir| pos + dir))
+            .filter(|neighbor| !self.positions.contains(neighbor))
+            .count()
+    }
+
+    /// Number of straight sides of the outline (including the outlines of holes),
+    /// counted as the number of corners.
+    pub fn sides(&self) -> usize {
+        self.positions
+            .iter()
+            .map(|&pos| {
+                DIRECTIONS
+                    .into_iter()
+                    .circular_tuple_windows()
+                    .filter(|&(a, b)| {
+                        let side_a = self.positions.contains(&(pos + a));
+                        let side_b = self.positions.contains(&(pos + b));
+                        let diagonal = self.positions.contains(&(pos + a + b));
+                        // outer corner, or inner corner where only the diagonal is missing
+                        (!side_a && !side_b) || (side_a && side_b && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// Labels the connected regions of equal values in a map.
+///
+/// ```
+/// # use adventofcode::common::grid::{parse_map, regions::regions};
+/// let map = parse_map("AAB\nABB\nCCB".lines(), |c| c);
+/// let regions = regions(&map);
+/// assert_eq!(regions.len(), 3);
+/// let b = regions.iter().find(|region| region.value == 'B').unwrap();
+/// assert_eq!((b.area(), b.perimeter(), b.sides()), (4, 10, 8));
+/// ```
+pub fn regions<T>(map: &HashMap<IVec2, T>) -> Vec<Region<T>>
+where
+    T: Clone + Eq,
+{
+    find_regions(map.keys().copied().collect(), |pos| map.get(&pos))
+}
+
+/// Labels the connected regions of equal values in a dense grid, indexed as `grid[y][x]`.
+pub fn regions_in_grid<T>(grid: &[Vec<T>]) -> Vec<Region<T>>
+where
+    T: Clone + Eq,
+{
+    let positions = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| (0..row.len()).map(move |x| IVec2::new(x as i32, y as i32)))
+        .collect();
+    find_regions(positions, |pos| {
+        grid.get(usize::try_from(pos.y).ok()?)?
+            .get(usize::try_from(pos.x).ok()?)
+    })
+}
+
+fn find_regions<'a, T, F>(positions: Vec<IVec2>, value_at: F) -> Vec<Region<T>>
+where
+    T: Clone + Eq + 'a,
+    F: Fn(IVec2) -> Option<&'a T>,
+{
+    let indices = positions
+        .iter()
+        .enumerate()
+        .map(|(i, &pos)| (pos, i))
+        .collect::<HashMap<_, _>>();
+    let mut sets = UnionFind::new(positions.len());
+
+    for (i, &pos) in positions.iter().enumerate() {
+        let value = value_at(pos);
+        // right and down are enough, the other directions are covered by the neighbors
+        for neighbor in [pos + RIGHT, pos + DOWN] {
+            if let Some(&j) = indices.get(&neighbor) {
+                if value_at(neighbor) == value {
+                    sets.union(i, j);
+                }
+            }
+        }
+    }
+
+    sets.sets()
+        .into_iter()
+        .map(|set| Region {
+            value: value_at(positions[set[0]]).unwrap().clone(),
+            positions: set.into_iter().map(|i| positions[i]).collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid::{parse_map, parse_vec};
+
+    const EXAMPLE: &str = "\
+RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+    fn prices<T>(regions: &[Region<T>]) -> (usize, usize) {
+        (
+            regions.iter().map(|r| r.area() * r.perimeter()).sum(),
+            regions.iter().map(|r| r.area() * r.sides()).sum(),
+        )
+    }
+
+    #[test]
+    fn test_garden_plots() {
+        // 2024 day 12 examples
+        let map = parse_map("AAAA\nBBCD\nBBCC\nEEEC".lines(), |c| c);
+        assert_eq!(prices(&regions(&map)), (140, 80));
+
+        let map = parse_map("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO".lines(), |c| c);
+        let regions = regions(&map);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(prices(&regions), (772, 436));
+
+        let map = parse_map(EXAMPLE.lines(), |c| c);
+        assert_eq!(prices(&super::regions(&map)), (1930, 1206));
+    }
+
+    #[test]
+    fn test_grid_and_map_agree() {
+        let grid = parse_vec(EXAMPLE.lines(), |c| c);
+        let map = parse_map(EXAMPLE.lines(), |c| c);
+        let sorted = |regions: Vec<Region<char>>| {
+            regions
+                .into_iter()
+                .map(|r| {
+                    (
+                        r.value,
+                        r.positions
+                            .into_iter()
+                            .map(|p| (p.y, p.x))
+                            .sorted()
+                            .collect_vec(),
+                    )
+                })
+                .sorted()
+                .collect_vec()
+        };
+        assert_eq!(sorted(regions_in_grid(&grid)), sorted(regions(&map)));
+        assert_eq!(regions_in_grid(&grid).len(), 11);
+    }
+
+    #[test]
+    fn test_sides_with_touching_holes() {
+        // two holes touching at a corner count as separate sides
+        let map = parse_map(
+            "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA".lines(),
+            |c| c,
+        );
+        let a = regions(&map)
+            .into_iter()
+            .find(|region| region.value == 'A')
+            .unwrap();
+        assert_eq!(a.sides(), 12);
+        assert_eq!(a.area() * a.sides(), 28 * 12);
+    }
+}
@@ -0,0 +1,128 @@
+//! The 24 rotations of a cube, for 3D puzzles where the orientation is unknown.
+
+use glam::IVec3;
+
+/// A rotation by multiples of 90 degrees, which maps the x, y and z axes to the given vectors.
+///
+/// ```
+/// # use adventofcode::common::grid::rotation::Rotation;
+/// # use glam::IVec3;
+/// let rotations = Rotation::all();
+/// let v = IVec3::new(1, 2, 3);
+/// assert_eq!(rotations[0].apply(v), v);
+/// for rotation in rotations {
+///     assert_eq!(rotation.inverse().apply(rotation.apply(v)), v);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    pub x: IVec3,
+    pub y: IVec3,
+    pub z: IVec3,
+}
+
+impl Rotation {
+    pub const IDENTITY: Rotation = Rotation {
+        x: IVec3::X,
+        y: IVec3::Y,
+        z: IVec3::Z,
+    };
+
+    /// Returns all 24 rotations, starting with the identity.
+    pub fn all() -> [Rotation; 24] {
+        const AXES: [IVec3; 6] = [
+            IVec3::X,
+            IVec3::NEG_X,
+            IVec3::Y,
+            IVec3::NEG_Y,
+            IVec3::Z,
+            IVec3::NEG_Z,
+        ];
+
+        let mut rotations = [Self::IDENTITY; 24];
+        let mut i = 0;
+        for x in AXES {
+            for y in AXES.into_iter().filter(|y| x.dot(*y) == 0) {
+                // the cross product keeps the coordinate system right-handed (no mirroring)
+                rotations[i] = Rotation {
+                    x,
+                    y,
+                    z: x.cross(y),
+                };
+                i += 1;
+            }
+        }
+        rotations
+    }
+
+    pub fn apply(&self, v: IVec3) -> IVec3 {
+        self.x * v.x + self.y * v.y + self.z * v.z
+    }
+
+    pub fn inverse(&self) -> Rotation {
+        Rotation {
+            x: IVec3::new(self.x.x, self.y.x, self.z.x),
+            y: IVec3::new(self.x.y, self.y.y, self.z.y),
+            z: IVec3::new(self.x.z, self.y.z, self.z.z),
+        }
+    }
+
+    /// Returns the rotation that applies `self` first and `other` second.
+    pub fn then(&self, other: &Rotation) -> Rotation {
+        Rotation {
+            x: other.apply(self.x),
+            y: other.apply(self.y),
+            z: other.apply(self.z),
+        }
+    }
+}
+
+/// All orientations of a vector.
+pub trait Rotations: Sized {
+    /// Returns the vector rotated with each of the 24 rotations, in the order of [`Rotation::all`].
+    fn rotations(&self) -> [Self; 24];
+}
+
+impl Rotations for IVec3 {
+    fn rotations(&self) -> [Self; 24] {
+        Rotation::all().map(|rotation| rotation.apply(*self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_rotations_are_distinct_and_closed() {
+        let rotations = Rotation::all();
+        let set = rotations.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 24);
+
+        for a in &rotations {
+            // determinant 1, so no mirroring
+            assert_eq!(a.x.cross(a.y).dot(a.z), 1);
+            for b in &rotations {
+                assert!(set.contains(&a.then(b)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_vector_rotations() {
+        let v = IVec3::new(1, 2, 3);
+        let rotated = v.rotations();
+        assert_eq!(rotated.iter().collect::<HashSet<_>>().len(), 24);
+        assert!(rotated.contains(&IVec3::new(-2, 1, 3)));
+        assert!(!rotated.contains(&IVec3::new(2, 1, 3)));
+        assert!(rotated
+            .iter()
+            .all(|r| r.abs().element_sum() == 6 && r.length_squared() == 14));
+
+        // composing rotations is the same as applying them one after another
+        let (a, b) = (Rotation::all()[5], Rotation::all()[17]);
+        assert_eq!(a.then(&b).apply(v), b.apply(a.apply(v)));
+    }
+}
@@ -0,0 +1,352 @@
+//! Hexagonal grids with axial coordinates: <https://www.redblobgames.com/grids/hexagons/>
+//!
+//! A hex is stored as `q` and `r`, the third cube coordinate is `s = -q - r`.
+//! Coordinates and neighbors are the same for both orientations of the hexagons, only the
+//! names of the directions and the rendering differ:
+//!
+//! - [`Orientation::Flat`] (flat top): `n`, `ne`, `se`, `s`, `sw`, `nw`
+//! - [`Orientation::Pointy`] (pointy top): `e`, `ne`, `nw`, `w`, `sw`, `se`
+
+use std::{
+    collections::HashMap,
+    ops::{Add, AddAssign, Mul, Neg, Sub},
+};
+
+use glam::IVec3;
+use itertools::Itertools;
+
+use crate::aoc::output;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// Flat top, hexes form columns
+    Flat,
+    /// Pointy top, hexes form rows
+    Pointy,
+}
+
+/// A hex in axial coordinates.
+///
+/// ```
+/// # use adventofcode::common::hex::{Hex, Orientation};
+/// let path = Hex::parse_path("ne,ne,s,s", Orientation::Flat).unwrap();
+/// let end = path.into_iter().fold(Hex::ORIGIN, |hex, step| hex + step);
+/// assert_eq!(end.distance(&Hex::ORIGIN), 2);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Offsets to the neighbors, counterclockwise starting with `se` (flat) or `e` (pointy).
+pub const DIRECTIONS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+const FLAT_NAMES: [&str; 6] = ["se", "ne", "n", "nw", "sw", "s"];
+const POINTY_NAMES: [&str; 6] = ["e", "ne", "nw", "w", "sw", "se"];
+
+impl Hex {
+    pub const ORIGIN: Hex = Hex::new(0, 0);
+
+    pub const fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    /// The third cube coordinate.
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn from_cube(cube: IVec3) -> Self {
+        assert_eq!(cube.element_sum(), 0, "Invalid cube coordinates: {}", cube);
+        Self::new(cube.x, cube.y)
+    }
+
+    pub fn to_cube(self) -> IVec3 {
+        IVec3::new(self.q, self.r, self.s())
+    }
+
+    /// Parses a direction name like `ne` to the offset of the neighbor in that direction.
+    ///
+    /// ```
+    /// # use adventofcode::common::hex::{Hex, Orientation};
+    /// assert_eq!(Hex::parse_direction("n", Orientation::Flat), Ok(Hex::new(0, -1)));
+    /// assert_eq!(Hex::parse_direction("w", Orientation::Pointy), Ok(Hex::new(-1, 0)));
+    /// assert!(Hex::parse_direction("n", Orientation::Pointy).is_err());
+    /// ```
+    pub fn parse_direction(name: &str, orientation: Orientation) -> Result<Hex, String> {
+        direction_names(orientation)
+            .iter()
+            .position(|&n| n == name)
+            .map(|i| DIRECTIONS[i])
+            .ok_or_else(|| {
+                format!(
+                    "Invalid direction '{}', chose one of: {}",
+                    name,
+                    direction_names(orientation).join(", ")
+                )
+            })
+    }
+
+    /// Parses a sequence of directions, separated by commas (`ne,s,nw`) or not (`esenee`).
+    ///
+    /// ```
+    /// # use adventofcode::common::hex::{Hex, Orientation};
+    /// let steps = Hex::parse_path("esew", Orientation::Pointy).unwrap();
+    /// assert_eq!(steps, vec![Hex::new(1, 0), Hex::new(0, 1), Hex::new(-1, 0)]);
+    /// ```
+    pub fn parse_path(path: &str, orientation: Orientation) -> Result<Vec<Hex>, String> {
+        // two letter names first, so "ne" isn't read as "n" followed by "e"
+        let names = direction_names(orientation)
+            .into_iter()
+            .sorted_by_key(|name| std::cmp::Reverse(name.len()))
+            .collect_vec();
+        let mut steps = vec![];
+        let mut rest = path.trim().trim_matches(',');
+
+        while !rest.is_empty() {
+            let name = names
+                .iter()
+                .find(|name| rest.starts_with(*name))
+                .ok_or_else(|| format!("Invalid direction at '{}'", rest))?;
+            steps.push(Self::parse_direction(name, orientation)?);
+            rest = rest[name.len()..].trim_start_matches([',', ' ']);
+        }
+
+        Ok(steps)
+    }
+
+    pub fn neighbors(&self) -> [Hex; 6] {
+        DIRECTIONS.map(|direction| *self + direction)
+    }
+
+    /// Returns the number of steps between two hexes.
+    pub fn distance(&self, other: &Hex) -> u32 {
+        let diff = (*self - *other).to_cube();
+        diff.abs().max_element() as u32
+    }
+
+    /// Returns the hexes at exactly `radius` steps, counterclockwise.
+    ///
+    /// ```
+    /// # use adventofcode::common::hex::Hex;
+    /// let ring = Hex::new(2, -1).ring(2);
+    /// assert_eq!(ring.len(), 12);
+    /// assert!(ring.iter().all(|hex| hex.distance(&Hex::new(2, -1)) == 2));
+    /// ```
+    pub fn ring(&self, radius: u32) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let mut hex = *self + DIRECTIONS[4] * radius as i32;
+        let mut ring = Vec::with_capacity(6 * radius as usize);
+        for direction in DIRECTIONS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex += direction;
+            }
+        }
+        ring
+    }
+
+    /// Returns all hexes within `radius` steps, ring by ring starting with `self`.
+    pub fn spiral(&self, radius: u32) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    /// Position of the hex in a text rendering, see [`render`].
+    fn text_position(&self, orientation: Orientation) -> (i32, i32) {
+        match orientation {
+            Orientation::Flat => (2 * self.q, 2 * self.r + self.q),
+            Orientation::Pointy => (2 * self.q + self.r, self.r),
+        }
+    }
+}
+
+fn direction_names(orientation: Orientation) -> [&'static str; 6] {
+    match orientation {
+        Orientation::Flat => FLAT_NAMES,
+        Orientation::Pointy => POINTY_NAMES,
+    }
+}
+
+impl Add for Hex {
+    type Output = Hex;
+
+    fn add(self, other: Hex) -> Hex {
+        Hex::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, other: Hex) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Hex;
+
+    fn sub(self, other: Hex) -> Hex {
+        Hex::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Mul<i32> for Hex {
+    type Output = Hex;
+
+    fn mul(self, factor: i32) -> Hex {
+        Hex::new(self.q * factor, self.r * factor)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Hex;
+
+    fn neg(self) -> Hex {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+/// Renders hexes as text lines, every hex is one character and neighbors are
+/// next to each other (pointy top) or above each other (flat top).
+///
+/// ```
+/// # use adventofcode::common::hex::{render, Hex, Orientation};
+/// let hexes = Hex::ORIGIN.spiral(1);
+/// let lines = render(&hexes, Orientation::Pointy, |hex| if hex == Hex::ORIGIN { 'O' } else { '.' });
+/// assert_eq!(lines, vec![" . .", ". O .", " . ."]);
+/// ```
+pub fn render<F>(hexes: &[Hex], orientation: Orientation, map_hex: F) -> Vec<String>
+where
+    F: Fn(Hex) -> char,
+{
+    let positions = hexes
+        .iter()
+        .map(|hex| (hex.text_position(orientation), map_hex(*hex)))
+        .collect::<HashMap<_, _>>();
+    let Some((min_x, max_x)) = positions.keys().map(|&(x, _)| x).minmax().into_option() else {
+        return vec![];
+    };
+    let (min_y, max_y) = positions
+        .keys()
+        .map(|&(_, y)| y)
+        .minmax()
+        .into_option()
+        .unwrap();
+
+    (min_y..=max_y)
+        .map(|y| {
+            (min_x..=max_x)
+                .map(|x| positions.get(&(x, y)).copied().unwrap_or(' '))
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Prints hexes to the debug output, see [`render`].
+pub fn print_hexes<F>(hexes: &[Hex], orientation: Orientation, map_hex: F)
+where
+    F: Fn(Hex) -> char,
+{
+    if output::is_debug_enabled() {
+        for line in render(hexes, orientation, map_hex) {
+            output::println_debug(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn walk(path: &str, orientation: Orientation) -> Hex {
+        Hex::parse_path(path, orientation)
+            .unwrap()
+            .into_iter()
+            .fold(Hex::ORIGIN, |hex, step| hex + step)
+    }
+
+    #[test]
+    fn test_flat_paths() {
+        // 2017 day 11 examples
+        assert_eq!(
+            walk("ne,ne,ne", Orientation::Flat).distance(&Hex::ORIGIN),
+            3
+        );
+        assert_eq!(walk("ne,ne,sw,sw", Orientation::Flat), Hex::ORIGIN);
+        assert_eq!(
+            walk("ne,ne,s,s", Orientation::Flat).distance(&Hex::ORIGIN),
+            2
+        );
+        assert_eq!(
+            walk("se,sw,se,sw,sw", Orientation::Flat).distance(&Hex::ORIGIN),
+            3
+        );
+    }
+
+    #[test]
+    fn test_pointy_paths() {
+        // 2020 day 24 examples
+        assert_eq!(walk("nwwswee", Orientation::Pointy), Hex::ORIGIN);
+        assert_eq!(walk("esew", Orientation::Pointy), Hex::new(0, 1));
+        assert_eq!(
+            Hex::parse_path("nex", Orientation::Pointy),
+            Err("Invalid direction at 'x'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_neighbors_and_cube() {
+        let hex = Hex::new(3, -5);
+        assert!(hex.neighbors().iter().all(|n| n.distance(&hex) == 1));
+        assert_eq!(Hex::from_cube(hex.to_cube()), hex);
+        assert_eq!(hex.to_cube(), IVec3::new(3, -5, 2));
+        // opposite directions cancel out
+        for i in 0..3 {
+            assert_eq!(DIRECTIONS[i], -DIRECTIONS[i + 3]);
+        }
+    }
+
+    #[test]
+    fn test_rings() {
+        let center = Hex::new(-1, 2);
+        assert_eq!(center.ring(0), vec![center]);
+        for radius in 1..5 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.iter().unique().count(), 6 * radius as usize);
+            assert!(ring.iter().all(|hex| hex.distance(&center) == radius));
+            // consecutive hexes are neighbors
+            assert!(ring
+                .iter()
+                .circular_tuple_windows()
+                .all(|(a, b)| a.distance(b) == 1));
+        }
+        let spiral = center.spiral(3).into_iter().collect::<HashSet<_>>();
+        assert_eq!(spiral.len(), 1 + 6 + 12 + 18);
+    }
+
+    #[test]
+    fn test_render_flat() {
+        let lines = render(&Hex::ORIGIN.spiral(1), Orientation::Flat, |hex| {
+            if hex == Hex::ORIGIN {
+                'O'
+            } else {
+                '.'
+            }
+        });
+        assert_eq!(lines, vec!["  .", ".   .", "  O", ".   .", "  ."]);
+    }
+}
@@ -0,0 +1,415 @@
+//! Exact linear algebra with integers and rationals, for puzzles where floats aren't precise
+//! enough, like systems of equations with large coordinates.
+//!
+//! The rational functions are generic over the integer type, use `i128` for speed or
+//! [`num::BigInt`] if intermediate values could overflow.
+
+use glam::I64Vec2;
+use num::{rational::Ratio, Integer, Zero};
+
+/// Solves the linear system `matrix * x = values` exactly with Gaussian elimination.
+///
+/// The matrix can have more rows than unknowns, as long as the equations don't contradict
+/// each other. Returns `None` if there is no solution or no unique solution.
+///
+/// ```
+/// # use adventofcode::common::linalg::solve;
+/// # use num::rational::Ratio;
+/// // 2x + y = 3, x - y = 3
+/// let solution = solve(&[vec![2, 1], vec![1, -1]], &[3, 3]).unwrap();
+/// assert_eq!(solution, vec![Ratio::from(2), Ratio::from(-1)]);
+/// assert_eq!(solve(&[vec![1, 1], vec![2, 2]], &[1, 3]), None);
+/// ```
+pub fn solve<T>(matrix: &[Vec<T>], values: &[T]) -> Option<Vec<Ratio<T>>>
+where
+    T: Clone + Integer,
+{
+    assert_eq!(matrix.len(), values.len(), "Every row needs a value");
+    let unknowns = matrix.first().map_or(0, Vec::len);
+
+    // augmented matrix with the values as the last column
+    let mut rows = matrix
+        .iter()
+        .zip(values)
+        .map(|(row, value)| {
+            assert_eq!(row.len(), unknowns, "All rows need the same length");
+            row.iter()
+                .chain([value])
+                .map(|x| Ratio::from_integer(x.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    for col in 0..unknowns {
+        let pivot = (col..rows.len()).find(|&row| !rows[row][col].is_zero())?;
+        rows.swap(col, pivot);
+
+        let pivot_value = rows[col][col].clone();
+        for value in rows[col].iter_mut() {
+            *value = value.clone() / pivot_value.clone();
+        }
+
+        let pivot_row = rows[col].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != col && !row[col].is_zero() {
+                let factor = row[col].clone();
+                for (value, pivot) in row.iter_mut().zip(&pivot_row).skip(col) {
+                    *value = value.clone() - factor.clone() * pivot.clone();
+                }
+            }
+        }
+    }
+
+    // remaining rows are 0 = value, which only works out if the value is 0 as well
+    if rows[unknowns..].iter().any(|row| !row[unknowns].is_zero()) {
+        return None;
+    }
+
+    Some(
+        rows.into_iter()
+            .take(unknowns)
+            .map(|row| row[unknowns].clone())
+            .collect(),
+    )
+}
+
+/// Solves the linear system like [`solve`], but only returns whole number solutions.
+///
+/// ```
+/// # use adventofcode::common::linalg::solve_integer;
+/// assert_eq!(solve_integer(&[vec![2, 1], vec![1, -1]], &[3, 3]), Some(vec![2, -1]));
+/// assert_eq!(solve_integer(&[vec![2, 0], vec![0, 1]], &[3, 3]), None);
+/// ```
+pub fn solve_integer<T>(matrix: &[Vec<T>], values: &[T]) -> Option<Vec<T>>
+where
+    T: Clone + Integer,
+{
+    solve(matrix, values)?
+        .into_iter()
+        .map(|x| x.is_integer().then(|| x.to_integer()))
+        .collect()
+}
+
+/// Calculates the determinant of a square matrix with the fraction-free Bareiss algorithm:
+/// <https://en.wikipedia.org/wiki/Bareiss_algorithm>
+///
+/// ```
+/// # use adventofcode::common::linalg::determinant;
+/// assert_eq!(determinant(&[vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]]), 49);
+/// assert_eq!(determinant(&[vec![1, 2], vec![2, 4]]), 0);
+/// ```
+pub fn determinant<T>(matrix: &[Vec<T>]) -> T
+where
+    T: Clone + Integer,
+{
+    let size = matrix.len();
+    assert!(
+        matrix.iter().all(|row| row.len() == size),
+        "Matrix needs to be square"
+    );
+
+    let mut rows = matrix.to_vec();
+    let mut negate = false;
+    let mut previous = T::one();
+
+    for k in 0..size {
+        let Some(pivot) = (k..size).find(|&row| !rows[row][k].is_zero()) else {
+            return T::zero();
+        };
+        if pivot != k {
+            rows.swap(k, pivot);
+            negate = !negate;
+        }
+
+        for i in k + 1..size {
+            for j in k + 1..size {
+                // the division is always exact
+                rows[i][j] = (rows[i][j].clone() * rows[k][k].clone()
+                    - rows[i][k].clone() * rows[k][j].clone())
+                    / previous.clone();
+            }
+        }
+        previous = rows[k][k].clone();
+    }
+
+    if negate {
+        T::zero() - previous
+    } else {
+        previous
+    }
+}
+
+/// Intersects the lines `a + t * a_dir` and `b + u * b_dir` and returns `(t, u)`,
+/// or `None` if the lines are parallel (or the same line).
+///
+/// ```
+/// # use adventofcode::common::linalg::{line_intersection, point_on_line};
+/// # use glam::I64Vec2;
+/// # use num::rational::Ratio;
+/// let (a, a_dir) = (I64Vec2::new(0, 0), I64Vec2::new(2, 1));
+/// let (b, b_dir) = (I64Vec2::new(0, 3), I64Vec2::new(1, -1));
+/// let (t, u) = line_intersection(a, a_dir, b, b_dir).unwrap();
+/// assert_eq!((t, u), (Ratio::from(1), Ratio::from(2)));
+/// assert_eq!(point_on_line(a, a_dir, t), [Ratio::from(2), Ratio::from(1)]);
+/// ```
+pub fn line_intersection(
+    a: I64Vec2,
+    a_dir: I64Vec2,
+    b: I64Vec2,
+    b_dir: I64Vec2,
+) -> Option<(Ratio<i128>, Ratio<i128>)> {
+    let cross = |v: I64Vec2, w: I64Vec2| v.x as i128 * w.y as i128 - v.y as i128 * w.x as i128;
+    let denominator = cross(a_dir, b_dir);
+    if denominator == 0 {
+        return None;
+    }
+
+    let diff = b - a;
+    Some((
+        Ratio::new(cross(diff, b_dir), denominator),
+        Ratio::new(cross(diff, a_dir), denominator),
+    ))
+}
+
+/// Returns the point `start + t * dir` with exact coordinates.
+pub fn point_on_line(start: I64Vec2, dir: I64Vec2, t: Ratio<i128>) -> [Ratio<i128>; 2] {
+    [
+        Ratio::from(start.x as i128) + t * dir.x as i128,
+        Ratio::from(start.y as i128) + t * dir.y as i128,
+    ]
+}
+
+/// Multiplies two integer matrices modulo `modulus`.
+pub fn matrix_mul(a: &[Vec<i64>], b: &[Vec<i64>], modulus: i64) -> Vec<Vec<i64>> {
+    let modulus = modulus as i128;
+    a.iter()
+        .map(|row| {
+            (0..b[0].len())
+                .map(|col| {
+                    row.iter().zip(b).fold(0, |sum, (&x, b_row)| {
+                        (sum + x as i128 * b_row[col] as i128).rem_euclid(modulus)
+                    }) as i64
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Calculates `matrix ^ exp` modulo `modulus` with exponentiation by squaring,
+/// like [`crate::common::math::mod_pow`] for numbers.
+///
+/// ```
+/// # use adventofcode::common::linalg::matrix_pow;
+/// // fibonacci numbers
+/// let fibonacci = matrix_pow(&[vec![1, 1], vec![1, 0]], 90, i64::MAX);
+/// assert_eq!(fibonacci[0][1], 2_880_067_194_370_816_120);
+/// ```
+pub fn matrix_pow(matrix: &[Vec<i64>], mut exp: u64, modulus: i64) -> Vec<Vec<i64>> {
+    let mut result = (0..matrix.len())
+        .map(|row| {
+            (0..matrix.len())
+                .map(|col| if row == col { 1 % modulus } else { 0 })
+                .collect()
+        })
+        .collect::<Vec<_>>();
+    let mut base = matrix
+        .iter()
+        .map(|row| row.iter().map(|x| x.rem_euclid(modulus)).collect())
+        .collect::<Vec<_>>();
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = matrix_mul(&result, &base, modulus);
+        }
+        base = matrix_mul(&base, &base, modulus);
+        exp >>= 1;
+    }
+
+    result
+}
+
+/// Returns the `n`th value (starting at 0) of the linear recurrence
+/// `x[n] = coefficients[0] * x[n - 1] + coefficients[1] * x[n - 2] + ...` modulo `modulus`,
+/// where the sequence starts with `initial` (one value for every coefficient).
+///
+/// ```
+/// # use adventofcode::common::linalg::linear_recurrence;
+/// // fibonacci numbers
+/// assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 10, i64::MAX), 55);
+/// assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 1_000_000_000_000, 1_000_000_007), 730_695_249);
+/// ```
+pub fn linear_recurrence(coefficients: &[i64], initial: &[i64], n: u64, modulus: i64) -> i64 {
+    let order = coefficients.len();
+    assert_eq!(
+        initial.len(),
+        order,
+        "Every coefficient needs an initial value"
+    );
+    if n < order as u64 {
+        return initial[n as usize].rem_euclid(modulus);
+    }
+
+    // the companion matrix moves the window of the last `order` values one step forward
+    let mut companion = vec![vec![0; order]; order];
+    companion[0].clone_from_slice(coefficients);
+    for i in 1..order {
+        companion[i][i - 1] = 1;
+    }
+    let state = initial.iter().rev().map(|&x| vec![x]).collect::<Vec<_>>();
+
+    let power = matrix_pow(&companion, n - order as u64 + 1, modulus);
+    matrix_mul(&power, &state, modulus)[0][0]
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+
+    use super::*;
+
+    /// Cost of the tokens to win the prize of a 2024 day 13 claw machine.
+    fn claw_machine(a: (i64, i64), b: (i64, i64), prize: (i64, i64)) -> Option<i64> {
+        let presses = solve_integer(&[vec![a.0, b.0], vec![a.1, b.1]], &[prize.0, prize.1])?;
+        presses
+            .iter()
+            .all(|&x| x >= 0)
+            .then(|| presses[0] * 3 + presses[1])
+    }
+
+    #[test]
+    fn test_claw_machines() {
+        let machines = [
+            ((94, 34), (22, 67), (8400, 5400)),
+            ((26, 66), (67, 21), (12748, 12176)),
+            ((17, 86), (84, 37), (7870, 6450)),
+            ((69, 23), (27, 71), (18641, 10279)),
+        ];
+        let costs = machines.map(|(a, b, prize)| claw_machine(a, b, prize));
+        assert_eq!(costs, [Some(280), None, Some(200), None]);
+
+        let offset = 10_000_000_000_000;
+        let costs =
+            machines.map(|(a, b, prize)| claw_machine(a, b, (prize.0 + offset, prize.1 + offset)));
+        assert_eq!(costs.map(|cost| cost.is_some()), [false, true, false, true]);
+    }
+
+    const HAILSTONES: [([i64; 3], [i64; 3]); 5] = [
+        ([19, 13, 30], [-2, 1, -2]),
+        ([18, 19, 22], [-1, -1, -2]),
+        ([20, 25, 34], [-2, -2, -4]),
+        ([12, 31, 28], [-1, -2, -1]),
+        ([20, 19, 15], [1, -5, -3]),
+    ];
+
+    #[test]
+    fn test_hailstone_paths() {
+        // 2023 day 24 part 1: future crossings inside the test area
+        let area = Ratio::from(7)..=Ratio::from(27);
+        let crossings = HAILSTONES
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| HAILSTONES[i + 1..].iter().map(move |b| (a, b)))
+            .filter(|((a, a_dir), (b, b_dir))| {
+                let line = |p: &[i64; 3]| I64Vec2::new(p[0], p[1]);
+                let (a, a_dir, b, b_dir) = (line(a), line(a_dir), line(b), line(b_dir));
+                line_intersection(a, a_dir, b, b_dir).is_some_and(|(t, u)| {
+                    let point = point_on_line(a, a_dir, t);
+                    t >= Ratio::zero()
+                        && u >= Ratio::zero()
+                        && point.iter().all(|x| area.contains(x))
+                })
+            })
+            .count();
+        assert_eq!(crossings, 2);
+    }
+
+    #[test]
+    fn test_hailstone_rock() {
+        // 2023 day 24 part 2: position p and velocity v of the rock satisfy
+        // (p - p_i) x (v - v_i) = 0, subtracting two of these equations makes them linear
+        let cross_matrix = |w: [BigInt; 3]| {
+            let zero = BigInt::zero();
+            [
+                [zero.clone(), w[2].clone(), -w[1].clone()],
+                [-w[2].clone(), zero.clone(), w[0].clone()],
+                [w[1].clone(), -w[0].clone(), zero],
+            ]
+        };
+        let cross = |a: [i64; 3], b: [i64; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let sub = |a: [i64; 3], b: [i64; 3]| [0, 1, 2].map(|i| BigInt::from(a[i] - b[i]));
+
+        let (p0, v0) = HAILSTONES[0];
+        let mut matrix = vec![];
+        let mut values = vec![];
+        for &(p, v) in &HAILSTONES[1..3] {
+            // p x (v - v0) + (p - p0) x v = p x v - p0 x v0
+            let by_position = cross_matrix(sub(v, v0));
+            let by_velocity = cross_matrix(sub(p0, p));
+            let (pv, pv0) = (cross(p, v), cross(p0, v0));
+            for i in 0..3 {
+                matrix.push(
+                    by_position[i]
+                        .iter()
+                        .chain(&by_velocity[i])
+                        .cloned()
+                        .collect::<Vec<_>>(),
+                );
+                values.push(BigInt::from(pv[i] - pv0[i]));
+            }
+        }
+
+        let rock = solve_integer(&matrix, &values).unwrap();
+        assert_eq!(rock, [24, 13, 10, -3, 1, 2].map(BigInt::from));
+    }
+
+    #[test]
+    fn test_overdetermined() {
+        let matrix = [vec![1, 0], vec![0, 1], vec![1, 1]];
+        assert_eq!(solve_integer(&matrix, &[2, 3, 5]), Some(vec![2, 3]));
+        assert_eq!(solve_integer(&matrix, &[2, 3, 4]), None);
+        assert_eq!(solve(&[vec![3i128]], &[2]), Some(vec![Ratio::new(2, 3)]));
+    }
+
+    #[test]
+    fn test_determinant_matches_solvability() {
+        let matrices = [
+            vec![vec![0, 1, 2], vec![1, 0, 3], vec![4, -3, 8]],
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]],
+            vec![vec![0, 0, 1], vec![0, 1, 0], vec![1, 0, 0]],
+        ];
+        assert_eq!(matrices.clone().map(|m| determinant(&m)), [-2, 0, -1]);
+        for matrix in matrices {
+            let solvable = solve(&matrix, &[1, 2, 3]).is_some();
+            assert_eq!(solvable, determinant(&matrix) != 0);
+        }
+        assert_eq!(determinant::<i64>(&[]), 1);
+    }
+
+    #[test]
+    fn test_linear_recurrence_against_brute_force() {
+        let coefficients = [2, -1, 3];
+        let initial = [1, 4, -2];
+        let mut sequence = initial.to_vec();
+        for n in 3..40 {
+            let next = (0..3)
+                .map(|i| coefficients[i] * sequence[n - 1 - i])
+                .sum::<i64>()
+                .rem_euclid(1009);
+            sequence.push(next);
+        }
+        for (n, &expected) in sequence.iter().enumerate() {
+            assert_eq!(
+                linear_recurrence(&coefficients, &initial, n as u64, 1009),
+                expected.rem_euclid(1009),
+                "n = {}",
+                n
+            );
+        }
+    }
+}
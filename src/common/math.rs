@@ -0,0 +1,205 @@
+/// Extended Euclidean algorithm: <https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm>
+///
+/// Returns `(gcd, x, y)` with `a * x + b * y == gcd`, where `gcd` is never negative.
+///
+/// ```
+/// # use adventofcode::common::math::extended_gcd;
+/// let (gcd, x, y) = extended_gcd(240, 46);
+/// assert_eq!(gcd, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// Returns the modular inverse of `a` modulo `modulus`, if `a` and `modulus` are coprime.
+///
+/// ```
+/// # use adventofcode::common::math::mod_inv;
+/// assert_eq!(mod_inv(3, 11), Some(4));
+/// assert_eq!(mod_inv(-3, 11), Some(7));
+/// assert_eq!(mod_inv(4, 8), None);
+/// ```
+pub fn mod_inv(a: i64, modulus: i64) -> Option<i64> {
+    let (gcd, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (gcd == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Calculates `base ^ exp % modulus` with exponentiation by squaring.
+/// The result is always in `0..modulus`.
+///
+/// ```
+/// # use adventofcode::common::math::mod_pow;
+/// assert_eq!(mod_pow(4, 13, 497), 445);
+/// assert_eq!(mod_pow(-2, 3, 5), 2);
+/// assert_eq!(mod_pow(7, 0, 1), 0);
+/// ```
+pub fn mod_pow(base: i64, mut exp: u64, modulus: i64) -> i64 {
+    let modulus = modulus as i128;
+    let mut base = (base as i128).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    result as i64
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder theorem:
+/// <https://en.wikipedia.org/wiki/Chinese_remainder_theorem>
+///
+/// The moduli don't need to be coprime. Returns `(x, lcm)` with the smallest non-negative
+/// solution `x` and the least common multiple of all moduli (all solutions are `x + k * lcm`),
+/// or `None` if the congruences contradict each other.
+///
+/// ```
+/// # use adventofcode::common::math::crt;
+/// assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt(&[(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt(&[(0, 4), (1, 6)]), None);
+/// ```
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    congruences
+        .iter()
+        .try_fold((0, 1), |(x, lcm), &(residue, modulus)| {
+            let residue = residue.rem_euclid(modulus);
+            let (gcd, p, _) = extended_gcd(lcm, modulus);
+            let diff = residue - x;
+            if diff % gcd != 0 {
+                return None;
+            }
+
+            let step = modulus / gcd;
+            // p * lcm ≡ gcd (mod modulus), so adding k * lcm with k = diff / gcd * p fixes the new congruence
+            let k = ((diff / gcd) as i128 * p as i128).rem_euclid(step as i128);
+            let new_lcm = lcm * step;
+            let new_x = (x as i128 + k * lcm as i128).rem_euclid(new_lcm as i128);
+            Some((new_x as i64, new_lcm))
+        })
+}
+
+/// Combines repeating events given as `(offset, period)`, where an event happens at
+/// `offset`, `offset + period`, `offset + 2 * period`, ...
+///
+/// Returns the first time at which all events happen at the same time, or `None` if they never do.
+/// Unlike the least common multiple, this also works if the offsets aren't aligned with the periods.
+///
+/// ```
+/// # use adventofcode::common::math::combine_cycles;
+/// assert_eq!(combine_cycles(&[(3, 3), (5, 5)]), Some(15));
+/// assert_eq!(combine_cycles(&[(1, 3), (3, 4), (19, 12)]), Some(19));
+/// assert_eq!(combine_cycles(&[(8, 2), (3, 3)]), Some(12));
+/// assert_eq!(combine_cycles(&[(0, 2), (1, 4)]), None);
+/// ```
+pub fn combine_cycles(cycles: &[(i64, i64)]) -> Option<i64> {
+    let (x, lcm) = crt(cycles)?;
+    let first_possible = cycles.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+    if x >= first_possible {
+        Some(x)
+    } else {
+        Some(x + (first_possible - x + lcm - 1) / lcm * lcm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::integer::Integer;
+
+    use super::*;
+
+    #[test]
+    fn test_extended_gcd_against_brute_force() {
+        for a in -30..=30i64 {
+            for b in -30..=30 {
+                let (gcd, x, y) = extended_gcd(a, b);
+                assert_eq!(gcd, a.gcd(&b), "gcd({}, {})", a, b);
+                assert_eq!(a * x + b * y, gcd, "bezout({}, {})", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_inv_against_brute_force() {
+        for modulus in 1..40i64 {
+            for a in -40..40 {
+                let expected = (0..modulus).find(|&x| (a * x).rem_euclid(modulus) == 1 % modulus);
+                assert_eq!(mod_inv(a, modulus), expected, "mod_inv({}, {})", a, modulus);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_pow_against_brute_force() {
+        for modulus in 1..20i64 {
+            for base in -10..10i64 {
+                for exp in 0..10 {
+                    let expected =
+                        (0..exp).fold(1 % modulus, |acc, _| (acc * base).rem_euclid(modulus));
+                    assert_eq!(mod_pow(base, exp as u64, modulus), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_pow_large() {
+        assert_eq!(mod_pow(2, 1_000_000, 1_000_000_007), 235_042_059);
+        assert_eq!(
+            mod_pow(123_456_789, 10u64.pow(18), (1 << 62) + 135),
+            3_775_878_063_045_643_970
+        );
+    }
+
+    #[test]
+    fn test_crt_against_brute_force() {
+        for m1 in 1..12i64 {
+            for m2 in 1..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let lcm = m1.lcm(&m2);
+                        let expected = (0..lcm)
+                            .find(|x| x % m1 == r1 && x % m2 == r2)
+                            .map(|x| (x, lcm));
+                        assert_eq!(crt(&[(r1, m1), (r2, m2)]), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_combine_cycles_against_brute_force() {
+        for p1 in 1..8i64 {
+            for p2 in 1..8 {
+                for o1 in 0..10 {
+                    for o2 in 0..10 {
+                        let expected = (0..200).find(|&t| {
+                            t >= o1 && t >= o2 && (t - o1) % p1 == 0 && (t - o2) % p2 == 0
+                        });
+                        assert_eq!(combine_cycles(&[(o1, p1), (o2, p2)]), expected);
+                    }
+                }
+            }
+        }
+    }
+}
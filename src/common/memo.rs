@@ -0,0 +1,215 @@
+//! Memoization of recursive functions, for dynamic programming solutions.
+//!
+//! A [`Memo`] is passed through the recursion instead of a bare `HashMap`, so the cache lookup
+//! and insert can't be forgotten on some path and hits and misses are counted for debugging.
+//! It isn't shared between threads: with rayon, every task creates its own memo (for example
+//! in `map` or with `map_init` if the cache stays valid between items), so no locking is needed.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    hash::Hash,
+    iter::Sum,
+    ops::Add,
+};
+
+/// Cache for the results of a pure function.
+///
+/// ```
+/// # use adventofcode::common::memo::Memo;
+/// fn fibonacci(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     memo.get_or_compute(n, |memo| match n {
+///         0 | 1 => n,
+///         _ => fibonacci(memo, n - 1) + fibonacci(memo, n - 2),
+///     })
+/// }
+///
+/// let mut memo = Memo::new();
+/// assert_eq!(fibonacci(&mut memo, 90), 2_880_067_194_370_816_120);
+/// assert_eq!(memo.stats().misses, 91);
+/// assert_eq!(memo.stats().hits, 88);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+/// Statistics of a [`Memo`], which can be added up over several memos.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Number of cached results
+    pub size: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached result for `key`, or computes and caches it with `compute`.
+    /// `compute` gets the memo to make recursive calls.
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Removes all cached results, the statistics are kept.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            size: self.cache.len(),
+        }
+    }
+}
+
+impl Stats {
+    /// Share of lookups that were answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            lookups => self.hits as f64 / lookups as f64,
+        }
+    }
+}
+
+impl Add for Stats {
+    type Output = Stats;
+
+    fn add(self, other: Stats) -> Stats {
+        Stats {
+            hits: self.hits + other.hits,
+            misses: self.misses + other.misses,
+            size: self.size + other.size,
+        }
+    }
+}
+
+impl Sum for Stats {
+    fn sum<I: Iterator<Item = Stats>>(iter: I) -> Stats {
+        iter.fold(Stats::default(), Add::add)
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hits), {} cached",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.size
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of paths through a grid moving only right and down.
+    fn paths(memo: &mut Memo<(u32, u32), u64>, x: u32, y: u32) -> u64 {
+        memo.get_or_compute((x, y), |memo| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                paths(memo, x - 1, y) + paths(memo, x, y - 1)
+            }
+        })
+    }
+
+    #[test]
+    fn test_grid_paths() {
+        let mut memo = Memo::new();
+        assert_eq!(paths(&mut memo, 16, 16), 601_080_390);
+        let stats = memo.stats();
+        assert_eq!(stats.size, 17 * 17 - 1);
+        assert_eq!(stats.misses as usize, stats.size);
+
+        // the second call is a single hit
+        assert_eq!(paths(&mut memo, 16, 16), 601_080_390);
+        assert_eq!(memo.stats().hits, stats.hits + 1);
+
+        memo.clear();
+        assert_eq!(memo.get(&(16, 16)), None);
+        assert_eq!(memo.stats().size, 0);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = [
+            Stats {
+                hits: 3,
+                misses: 1,
+                size: 1,
+            },
+            Stats {
+                hits: 0,
+                misses: 4,
+                size: 4,
+            },
+        ];
+        let total = stats.into_iter().sum::<Stats>();
+        assert_eq!(total.to_string(), "3 hits, 5 misses (37.5% hits), 5 cached");
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel() {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let (total, stats) = (0..20u32)
+            .into_par_iter()
+            .map(|n| {
+                let mut memo = Memo::new();
+                (paths(&mut memo, n, n), memo.stats())
+            })
+            .reduce(
+                || (0, Stats::default()),
+                |(a, stats_a), (b, stats_b)| (a + b, stats_a + stats_b),
+            );
+        let expected = (0..20u32)
+            .map(|n| paths(&mut Memo::new(), n, n))
+            .sum::<u64>();
+        assert_eq!(total, expected);
+        assert!(stats.hits > 0);
+    }
+}
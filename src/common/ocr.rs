@@ -1,176 +1,349 @@
-use super::grid::print_grid;
+use itertools::Itertools;
 
+use crate::aoc::output;
+
+/// A font with letters of a fixed height, as used for messages drawn by puzzles.
+pub struct Font {
+    /// Number of rows of every glyph.
+    pub height: usize,
+    /// Width of a letter including the spacing to the next letter, used for rendering.
+    pub cell_width: usize,
+    glyphs: &'static [(char, [&'static str; 10])],
+}
+
+/// The 6 rows high font used by most puzzles (for example 2016 day 8, 2019 day 8 or 2022 day 10).
+///
+/// Letters that never showed up in a puzzle (D, M, N, Q, T, V, W and X) are drawn in the same style.
+#[rustfmt::skip]
+pub const FONT_6: Font = Font {
+    height: 6,
+    cell_width: 5,
+    glyphs: &[
+        ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#", "", "", "", ""]),
+        ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###.", "", "", "", ""]),
+        ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##.", "", "", "", ""]),
+        ('D', ["###.", "#..#", "#..#", "#..#", "#..#", "###.", "", "", "", ""]),
+        ('E', ["####", "#...", "###.", "#...", "#...", "####", "", "", "", ""]),
+        ('F', ["####", "#...", "###.", "#...", "#...", "#...", "", "", "", ""]),
+        ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###", "", "", "", ""]),
+        ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#", "", "", "", ""]),
+        ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###", "", "", "", ""]),
+        ('J', ["..##", "...#", "...#", "...#", "#..#", ".##.", "", "", "", ""]),
+        ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#", "", "", "", ""]),
+        ('L', ["#...", "#...", "#...", "#...", "#...", "####", "", "", "", ""]),
+        ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#", "#...#", "", "", "", ""]),
+        ('N', ["#..#", "##.#", "#.##", "#..#", "#..#", "#..#", "", "", "", ""]),
+        ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##.", "", "", "", ""]),
+        ('P', ["###.", "#..#", "#..#", "###.", "#...", "#...", "", "", "", ""]),
+        ('Q', [".##.", "#..#", "#..#", "#..#", "#.##", ".###", "", "", "", ""]),
+        ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#", "", "", "", ""]),
+        ('S', [".###", "#...", "#...", ".##.", "...#", "###.", "", "", "", ""]),
+        ('T', ["#####", "..#..", "..#..", "..#..", "..#..", "..#..", "", "", "", ""]),
+        ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##.", "", "", "", ""]),
+        ('V', ["#...#", "#...#", "#...#", ".#.#.", ".#.#.", "..#..", "", "", "", ""]),
+        ('W', ["#...#", "#...#", "#...#", "#.#.#", "##.##", "#...#", "", "", "", ""]),
+        ('X', ["#...#", ".#.#.", "..#..", "..#..", ".#.#.", "#...#", "", "", "", ""]),
+        ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#..", "", "", "", ""]),
+        ('Z', ["####", "...#", "..#.", ".#..", "#...", "####", "", "", "", ""]),
+    ],
+};
+
+/// The 10 rows high font used by 2018 day 10.
+///
+/// Letters that never showed up in a puzzle (D, I, M, O, Q, S, T, U, V, W and Y) are drawn in the same style.
+#[rustfmt::skip]
+pub const FONT_10: Font = Font {
+    height: 10,
+    cell_width: 8,
+    glyphs: &[
+        ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+        ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+        ('D', ["#####.", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#####."]),
+        ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+        ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('I', ["..###.", "...#..", "...#..", "...#..", "...#..", "...#..", "...#..", "...#..", "...#..", "..###."]),
+        ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+        ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+        ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+        ('M', ["#....#", "##..##", "#.##.#", "#.##.#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+        ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+        ('O', [".####.", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", ".####."]),
+        ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+        ('Q', [".####.", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#..#.#", "#...#.", ".###.#"]),
+        ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+        ('S', [".####.", "#....#", "#.....", "#.....", ".####.", ".....#", ".....#", ".....#", "#....#", ".####."]),
+        ('T', ["######", "..##..", "..##..", "..##..", "..##..", "..##..", "..##..", "..##..", "..##..", "..##.."]),
+        ('U', ["#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", "#....#", ".####."]),
+        ('V', ["#....#", "#....#", "#....#", ".#..#.", ".#..#.", ".#..#.", "..##..", "..##..", "..##..", "..##.."]),
+        ('W', ["#....#", "#....#", "#....#", "#....#", "#....#", "#.##.#", "#.##.#", "##..##", "##..##", "#....#"]),
+        ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+        ('Y', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", "..##..", "..##..", "..##..", "..##.."]),
+        ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+    ],
+};
+
+/// Prints the image as debug output, captured grids get the decoded message as title.
 pub fn print_image(image: &[&[bool]]) {
-    print_grid(image, |b| match b {
-        false => ' ',
-        true => '█',
-    });
+    if output::is_debug_enabled() {
+        let lines = image
+            .iter()
+            .map(|row| row.iter().map(|&b| if b { '█' } else { ' ' }).collect())
+            .collect();
+        let title = if output::is_capturing_grids() {
+            read_message(image).ok()
+        } else {
+            None
+        };
+        output::println_grid(title, lines);
+    }
 }
 
-pub fn read_message(image: &[&[bool]]) -> String {
-    let char_width = match image.len() {
-        6 => 5,
-        10 => 6,
-        _ => panic!("Unknown char size"),
-    };
-    let line_length = image[0].len() / char_width;
+/// Reads the message of an image with [`FONT_6`] or [`FONT_10`], depending on the height of the image.
+///
+/// Letters are split at empty columns, so the spacing between letters doesn't matter.
+/// Letters without an empty column between them (like a Y followed by another letter) are split
+/// by matching the glyphs of the font. Returns an error with the bitmaps of all unknown glyphs.
+///
+/// ```
+/// # use adventofcode::common::ocr::{read_message, render_message};
+/// let image = render_message("HELLO");
+/// let image = image.iter().map(|row| row.as_slice()).collect::<Vec<_>>();
+/// assert_eq!(read_message(&image), Ok("HELLO".to_string()));
+/// ```
+pub fn read_message(image: &[&[bool]]) -> Result<String, String> {
+    match image.len() {
+        6 => read_message_with_font(image, &FONT_6),
+        10 => read_message_with_font(image, &FONT_10),
+        height => Err(format!("Unknown font with height {}", height)),
+    }
+}
 
-    #[cfg(debug_assertions)]
-    for line in image {
-        assert_eq!(line.len() / char_width, line_length);
-        assert_eq!(line.len() % char_width, 0);
+/// Reads the message of an image with the given font.
+pub fn read_message_with_font(image: &[&[bool]], font: &Font) -> Result<String, String> {
+    if image.len() != font.height {
+        return Err(format!(
+            "Image height {} doesn't match font height {}",
+            image.len(),
+            font.height
+        ));
     }
 
+    // every column as a bitmask with bit y set for a pixel in row y
+    let width = image.iter().map(|row| row.len()).max().unwrap_or(0);
+    let columns = (0..width)
+        .map(|x| {
+            image.iter().enumerate().fold(0, |column, (y, row)| {
+                column | (row.get(x).copied().unwrap_or(false) as u32) << y
+            })
+        })
+        .collect_vec();
+    let glyphs = font.glyph_columns();
+
     let mut message = String::new();
-    for i in 0..line_length {
-        let char = &image
+    let mut unknown = vec![];
+    for (empty, segment) in &columns.iter().chunk_by(|&&column| column == 0) {
+        if empty {
+            continue;
+        }
+
+        let segment = segment.copied().collect_vec();
+        match read_segment(&segment, &glyphs) {
+            Some(letters) => message.push_str(&letters),
+            None => {
+                message.push('?');
+                unknown.push(format!(
+                    "glyph {}:\n{}",
+                    unknown.len() + 1,
+                    format_columns(&segment, font.height)
+                ));
+            }
+        }
+    }
+
+    if unknown.is_empty() {
+        Ok(message)
+    } else {
+        Err(format!(
+            "Unknown glyphs in message \"{}\":\n{}",
+            message,
+            unknown.join("\n")
+        ))
+    }
+}
+
+/// Splits columns without empty columns into letters, trying longer glyphs first.
+fn read_segment(segment: &[u32], glyphs: &[(char, Vec<u32>)]) -> Option<String> {
+    if segment.is_empty() {
+        return Some(String::new());
+    }
+
+    glyphs
+        .iter()
+        .filter(|(_, columns)| segment.starts_with(columns))
+        .sorted_by_key(|(_, columns)| std::cmp::Reverse(columns.len()))
+        .find_map(|(c, columns)| {
+            read_segment(&segment[columns.len()..], glyphs).map(|rest| format!("{}{}", c, rest))
+        })
+}
+
+/// Renders a message with [`FONT_6`], as the inverse of [`read_message`].
+///
+/// Panics if the message contains characters that aren't in the font.
+pub fn render_message(message: &str) -> Vec<Vec<bool>> {
+    render_message_with_font(message, &FONT_6)
+}
+
+/// Renders a message with the given font, every letter uses `font.cell_width` columns.
+///
+/// Panics if the message contains characters that aren't in the font.
+pub fn render_message_with_font(message: &str, font: &Font) -> Vec<Vec<bool>> {
+    let mut image = vec![vec![false; message.chars().count() * font.cell_width]; font.height];
+
+    for (i, c) in message.chars().enumerate() {
+        let (_, rows) = font
+            .glyphs
+            .iter()
+            .find(|(glyph_char, _)| *glyph_char == c)
+            .unwrap_or_else(|| panic!("No glyph for '{}' in font", c));
+        for (y, row) in rows[..font.height].iter().enumerate() {
+            for (x, pixel) in row.chars().enumerate() {
+                image[y][i * font.cell_width + x] = pixel == '#';
+            }
+        }
+    }
+
+    image
+}
+
+impl Font {
+    /// Returns the glyphs as column bitmasks without empty columns on the left and right.
+    fn glyph_columns(&self) -> Vec<(char, Vec<u32>)> {
+        self.glyphs
             .iter()
-            .map(|line| &line[i * char_width..(i + 1) * char_width])
-            .collect::<Vec<_>>();
-        message.push(read_char(char));
+            .map(|(c, rows)| {
+                let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+                let columns = (0..width)
+                    .map(|x| {
+                        rows[..self.height]
+                            .iter()
+                            .enumerate()
+                            .fold(0, |column, (y, row)| {
+                                column | ((row.as_bytes()[x] == b'#') as u32) << y
+                            })
+                    })
+                    .skip_while(|&column| column == 0)
+                    .collect_vec();
+                let used = columns.len() - columns.iter().rev().take_while(|&&c| c == 0).count();
+                (*c, columns[..used].to_vec())
+            })
+            .collect()
     }
-    message
 }
 
-#[rustfmt::skip]
-fn read_char(char: &[&[bool]]) -> char {
-    match char {
-        [
-            [false, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, true, true, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false]
-        ] => 'A',
-        [
-            [true, true, true, false, false],
-            [true, false, false, true, false],
-            [true, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, true, true, false, false]
-        ] => 'B',
-        [
-            [false, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, false, false, true, false],
-            [false, true, true, false, false]
-        ] => 'C',
-        [
-            [true, true, true, true, false],
-            [true, false, false, false, false],
-            [true, true, true, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, true, true, true, false]
-        ] => 'E',
-        [
-            [true, true, true, true, false],
-            [true, false, false, false, false],
-            [true, true, true, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false]
-        ] => 'F',
-        [
-            [false, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, false, false],
-            [true, false, true, true, false],
-            [true, false, false, true, false],
-            [false, true, true, true, false]
-        ] => 'G',
-        [
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, true, true, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false]
-        ] => 'H',
-        [
-            [false, false, true, true, false],
-            [false, false, false, true, false],
-            [false, false, false, true, false],
-            [false, false, false, true, false],
-            [true, false, false, true, false],
-            [false, true, true, false, false]
-        ] => 'J',
-        [
-            [true, false, false, true, false],
-            [true, false, true, false, false],
-            [true, true, false, false, false],
-            [true, false, true, false, false],
-            [true, false, true, false, false],
-            [true, false, false, true, false]
-        ] => 'K',
-        [
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [true, true, true, true, false]
-        ] => 'L',
-        [
-            [false, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [false, true, true, false, false]
-        ] => 'O',
-        [
-            [true, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, true, true, false, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false]
-        ] => 'P',
-        [
-            [true, true, true, false, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, true, true, false, false],
-            [true, false, true, false, false],
-            [true, false, false, true, false]
-        ] => 'R',
-        [
-            [false, true, true, true, false],
-            [true, false, false, false, false],
-            [true, false, false, false, false],
-            [false, true, true, false, false],
-            [false, false, false, true, false],
-            [true, true, true, false, false]
-        ] => 'S',
-        [
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [true, false, false, true, false],
-            [false, true, true, false, false]
-        ] => 'U',
-        [
-            [true, false, false, false, true],
-            [true, false, false, false, true],
-            [false, true, false, true, false],
-            [false, false, true, false, false],
-            [false, false, true, false, false],
-            [false, false, true, false, false]
-        ] => 'Y',
-        [
-            [true, true, true, true, false],
-            [false, false, false, true, false],
-            [false, false, true, false, false],
-            [false, true, false, false, false],
-            [true, false, false, false, false],
-            [true, true, true, true, false]
-        ] => 'Z',
-        _ => '?',
+fn format_columns(columns: &[u32], height: usize) -> String {
+    (0..height)
+        .map(|y| {
+            columns
+                .iter()
+                .map(|column| if column & 1 << y != 0 { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    fn as_slices(image: &[Vec<bool>]) -> Vec<&[bool]> {
+        image.iter().map(|row| row.as_slice()).collect()
+    }
+
+    fn format_image(image: &[Vec<bool>]) -> Vec<String> {
+        image
+            .iter()
+            .map(|row| row.iter().map(|&p| if p { '#' } else { '.' }).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_glyphs_are_unique() {
+        for font in [&FONT_6, &FONT_10] {
+            let glyphs = font.glyph_columns();
+            assert_eq!(glyphs.len(), 26);
+            assert!(glyphs.iter().map(|(_, columns)| columns).all_unique());
+            // letters are split at empty columns, so a letter can't contain one
+            assert!(glyphs.iter().all(|(_, columns)| !columns.contains(&0)));
+        }
+    }
+
+    #[test]
+    fn test_read_rendered_alphabet() {
+        for font in [&FONT_6, &FONT_10] {
+            let image = render_message_with_font(ALPHABET, font);
+            assert_eq!(read_message(&as_slices(&image)), Ok(ALPHABET.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_read_all_pairs() {
+        // wide letters touch the following letter, which must still be split correctly
+        for (a, b) in ALPHABET.chars().cartesian_product(ALPHABET.chars()) {
+            let message = format!("{}{}", a, b);
+            let image = render_message(&message);
+            assert_eq!(read_message(&as_slices(&image)), Ok(message));
+        }
+    }
+
+    #[test]
+    fn test_render_matches_puzzle_layout() {
+        let image = render_message("EHYZ");
+        let expected = [
+            "####.#..#.#...#####.",
+            "#....#..#.#...#...#.",
+            "###..####..#.#...#..",
+            "#....#..#...#...#...",
+            "#....#..#...#..#....",
+            "####.#..#...#..####.",
+        ];
+        assert_eq!(format_image(&image), expected);
+    }
+
+    #[test]
+    fn test_varying_spacing_and_trailing_columns() {
+        let image = render_message_with_font("CAB", &FONT_10)
+            .into_iter()
+            .map(|row| {
+                // add some extra empty columns between and after the letters
+                let mut row = row;
+                row.splice(8..8, [false; 3]);
+                row.extend([false; 7]);
+                row
+            })
+            .collect_vec();
+        assert_eq!(read_message(&as_slices(&image)), Ok("CAB".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_glyph() {
+        let mut image = render_message("AB");
+        image[0][8] = true; // B with an extra pixel
+        let error = read_message(&as_slices(&image)).unwrap_err();
+        assert!(error.starts_with("Unknown glyphs in message \"A?\""));
+        assert!(error.contains("glyph 1:\n####\n#..#\n###.\n#..#\n#..#\n###."));
+    }
+
+    #[test]
+    fn test_unknown_font() {
+        let image = [[true].as_slice(); 7];
+        assert_eq!(
+            read_message(&image),
+            Err("Unknown font with height 7".to_string())
+        );
     }
 }
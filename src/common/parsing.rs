@@ -1,27 +1,41 @@
-use std::{fmt::Debug, io::BufRead, str::FromStr};
+//! Line helpers and reusable [`nom`] parsers for puzzle inputs.
+//!
+//! Solutions combine the parsers in this module with their own nom parsers and parse the
+//! whole input with [`parse_input`], which fails with the line and column of the error.
 
-/// Reads the input to a line iterator.
+use std::{collections::HashMap, fmt::Debug, hash::Hash, str::FromStr};
+
+use glam::{IVec2, IVec3};
+use nom::{
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0},
+    combinator::{all_consuming, cut, map, map_res, opt, recognize},
+    error::Error,
+    multi::separated_list1,
+    sequence::{pair, separated_pair, terminated, tuple},
+    IResult, Parser,
+};
+
+/// Returns an iterator over the lines of the input, borrowed from the input.
 ///
 /// ```
 /// # use adventofcode::common::parsing::lines_iter;
-/// let mut input = "aaa\nbbb".as_bytes();
-/// let mut lines = lines_iter(&mut input);
-/// # assert_eq!(lines.next(), Some("aaa".to_string()));
-/// # assert_eq!(lines.next(), Some("bbb".to_string()));
+/// let mut lines = lines_iter("aaa\r\nbbb\n");
+/// # assert_eq!(lines.next(), Some("aaa"));
+/// # assert_eq!(lines.next(), Some("bbb"));
 /// # assert_eq!(lines.next(), None);
 /// ```
-pub fn lines_iter(input: &mut dyn BufRead) -> impl Iterator<Item = String> + '_ {
-    input.lines().map(|l| l.unwrap())
+pub fn lines_iter(input: &str) -> impl Iterator<Item = &str> {
+    input.lines()
 }
 
-/// Reads the input to a string vector.
+/// Splits the input to a vector of borrowed lines.
 ///
 /// ```
 /// # use adventofcode::common::parsing::lines_vec;
-/// let lines = lines_vec(&mut "aaa\nbbb".as_bytes());
-/// # assert_eq!(lines, vec!["aaa".to_string(), "bbb".to_string()]);
+/// let lines = lines_vec("aaa\nbbb");
+/// # assert_eq!(lines, vec!["aaa", "bbb"]);
 /// ```
-pub fn lines_vec(input: &mut dyn BufRead) -> Vec<String> {
+pub fn lines_vec(input: &str) -> Vec<&str> {
     lines_iter(input).collect()
 }
 
@@ -29,10 +43,10 @@ pub fn lines_vec(input: &mut dyn BufRead) -> Vec<String> {
 ///
 /// ```
 /// # use adventofcode::common::parsing::parse_lines_vec;
-/// let lines: Vec<u8> = parse_lines_vec(&mut "1\n2\n3".as_bytes());
+/// let lines: Vec<u8> = parse_lines_vec("1\n2\n3");
 /// # assert_eq!(lines, vec![1, 2, 3]);
 /// ```
-pub fn parse_lines_vec<T>(input: &mut dyn BufRead) -> Vec<T>
+pub fn parse_lines_vec<T>(input: &str) -> Vec<T>
 where
     T: FromStr,
     <T as FromStr>::Err: Debug,
@@ -42,3 +56,262 @@ where
         .map(|l| l.parse().unwrap())
         .collect()
 }
+
+/// Splits the input into blocks separated by blank lines.
+///
+/// ```
+/// # use adventofcode::common::parsing::split_blocks;
+/// let blocks = split_blocks("a\nb\n\nc\r\n\r\nd\n").collect::<Vec<_>>();
+/// assert_eq!(blocks, vec!["a\nb", "c", "d"]);
+/// ```
+pub fn split_blocks(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .flat_map(|block| block.split("\r\n\r\n"))
+        .map(|block| block.trim_matches(['\r', '\n']))
+        .filter(|block| !block.is_empty())
+}
+
+/// Parses the whole input, apart from trailing whitespace.
+/// The error message contains the position of the error and the line with the error.
+///
+/// ```
+/// # use adventofcode::common::parsing::{lines, number, parse_all};
+/// assert_eq!(parse_all(lines(number::<u8>), "1\n2\n"), Ok(vec![1, 2]));
+/// assert_eq!(
+///     parse_all(lines(number::<u8>), "1\nx\n"),
+///     Err("Parse error at line 2, column 1 (expected Digit):\nx\n^".to_string())
+/// );
+/// ```
+pub fn parse_all<'a, O, F>(parser: F, input: &'a str) -> Result<O, String>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    match all_consuming(terminated(parser, multispace0))(input) {
+        Ok((_, output)) => Ok(output),
+        Err(nom::Err::Error(error) | nom::Err::Failure(error)) => Err(error_message(input, error)),
+        Err(nom::Err::Incomplete(_)) => Err("Parse error: incomplete input".to_string()),
+    }
+}
+
+/// Parses the whole input like [`parse_all`] and panics with the error message, for solutions.
+pub fn parse_input<'a, O, F>(parser: F, input: &'a str) -> O
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    parse_all(parser, input).unwrap_or_else(|error| panic!("{}", error))
+}
+
+fn error_message(input: &str, error: Error<&str>) -> String {
+    let offset = input.len() - error.input.len();
+    let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_number = input[..offset].matches('\n').count() + 1;
+    let column = input[line_start..offset].chars().count() + 1;
+    let line = input[line_start..].lines().next().unwrap_or("");
+    let reason = match error.code {
+        nom::error::ErrorKind::Eof => "unexpected input".to_string(),
+        code => format!("expected {}", code.description()),
+    };
+
+    format!(
+        "Parse error at line {}, column {} ({}):\n{}\n{}^",
+        line_number,
+        column,
+        reason,
+        line,
+        " ".repeat(column - 1)
+    )
+}
+
+/// Parses one item per line. The list ends at a blank line or the end of the input, any other
+/// line has to be a valid item.
+///
+/// ```
+/// # use adventofcode::common::parsing::{lines, number};
+/// # use nom::character::complete::alpha1;
+/// assert_eq!(lines(alpha1)("ab\ncd"), Ok(("", vec!["ab", "cd"])));
+/// assert_eq!(lines(number::<i32>)("1\r\n-2\n"), Ok(("\n", vec![1, -2])));
+/// ```
+pub fn lines<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_items(line_ending, parser)
+}
+
+/// Parses blocks separated by blank lines.
+///
+/// ```
+/// # use adventofcode::common::parsing::{blocks, lines, number};
+/// let (_, groups) = blocks(lines(number::<u32>))("1\n2\n\n3").unwrap();
+/// assert_eq!(groups, vec![vec![1, 2], vec![3]]);
+/// ```
+pub fn blocks<'a, O, F>(parser: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_items(pair(line_ending, line_ending), parser)
+}
+
+/// Like `separated_list1`, but an item after a separator can't fail without an error, so the
+/// error of the item is reported instead of unexpected input at the start of the item.
+fn separated_items<'a, O, O2, S, F>(
+    mut separator: S,
+    mut parser: F,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<O>>
+where
+    S: Parser<&'a str, O2, Error<&'a str>>,
+    F: Parser<&'a str, O, Error<&'a str>>,
+{
+    move |input| {
+        let (mut input, first) = parser.parse(input)?;
+        let mut items = vec![first];
+        loop {
+            let rest = match separator.parse(input) {
+                Ok((rest, _)) => rest,
+                Err(nom::Err::Error(_)) => return Ok((input, items)),
+                Err(error) => return Err(error),
+            };
+            // a trailing line ending or a blank line ends the list
+            if rest.is_empty() || rest.starts_with(['\n', '\r']) {
+                return Ok((input, items));
+            }
+            let (rest, item) = cut(|input| parser.parse(input))(rest)?;
+            items.push(item);
+            input = rest;
+        }
+    }
+}
+
+/// Parses an integer of any type with an optional sign.
+///
+/// ```
+/// # use adventofcode::common::parsing::number;
+/// assert_eq!(number::<i64>("-12 rest"), Ok((" rest", -12)));
+/// assert_eq!(number::<u8>("+7"), Ok(("", 7)));
+/// assert!(number::<u8>("256").is_err());
+/// ```
+pub fn number<T>(input: &str) -> IResult<&str, T>
+where
+    T: FromStr,
+{
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Parses numbers with any separator, like `space1` or `tag(", ")`.
+///
+/// ```
+/// # use adventofcode::common::parsing::numbers;
+/// # use nom::character::complete::{char, space1};
+/// assert_eq!(numbers::<u8, _, _>(space1)("1  2 3"), Ok(("", vec![1, 2, 3])));
+/// assert_eq!(numbers::<i32, _, _>(char(','))("4,-5"), Ok(("", vec![4, -5])));
+/// ```
+pub fn numbers<'a, T, S, O>(separator: S) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    T: FromStr,
+    S: Parser<&'a str, O, Error<&'a str>>,
+{
+    separated_list1(separator, number)
+}
+
+/// Parses a comma separated vector like `3,-4` or `3, -4`.
+///
+/// ```
+/// # use adventofcode::common::parsing::ivec2;
+/// # use glam::IVec2;
+/// assert_eq!(ivec2("3, -4"), Ok(("", IVec2::new(3, -4))));
+/// ```
+pub fn ivec2(input: &str) -> IResult<&str, IVec2> {
+    map(separated_pair(number, comma, number), |(x, y)| {
+        IVec2::new(x, y)
+    })(input)
+}
+
+/// Parses a comma separated vector like `1,2,-3`.
+///
+/// ```
+/// # use adventofcode::common::parsing::ivec3;
+/// # use glam::IVec3;
+/// assert_eq!(ivec3("1,2,-3"), Ok(("", IVec3::new(1, 2, -3))));
+/// ```
+pub fn ivec3(input: &str) -> IResult<&str, IVec3> {
+    map(
+        tuple((number, comma, number, comma, number)),
+        |(x, _, y, _, z)| IVec3::new(x, y, z),
+    )(input)
+}
+
+fn comma(input: &str) -> IResult<&str, &str> {
+    recognize(pair(char(','), space0))(input)
+}
+
+/// Parses lines of `<key><separator><value>` to a map.
+///
+/// ```
+/// # use adventofcode::common::parsing::{key_values, number};
+/// # use nom::{bytes::complete::tag, character::complete::alpha1};
+/// let (_, map) = key_values(alpha1, tag(": "), number::<u8>)("a: 1\nb: 2").unwrap();
+/// assert_eq!(map["b"], 2);
+/// ```
+pub fn key_values<'a, K, V, KP, SP, VP, S>(
+    key: KP,
+    separator: SP,
+    value: VP,
+) -> impl FnMut(&'a str) -> IResult<&'a str, HashMap<K, V>>
+where
+    K: Eq + Hash,
+    KP: Parser<&'a str, K, Error<&'a str>>,
+    SP: Parser<&'a str, S, Error<&'a str>>,
+    VP: Parser<&'a str, V, Error<&'a str>>,
+{
+    map(lines(separated_pair(key, separator, value)), |pairs| {
+        pairs.into_iter().collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_all_errors() {
+        let parser = || lines(separated_pair(number::<u8>, tag(" -> "), ivec2));
+        assert_eq!(
+            parse_all(parser(), "1 -> 2,3\n2 -> 4,5\n"),
+            Ok(vec![(1, IVec2::new(2, 3)), (2, IVec2::new(4, 5))])
+        );
+        assert_eq!(
+            parse_all(parser(), "1 -> 2,3\n2 => 4,5"),
+            Err("Parse error at line 2, column 2 (expected Tag):\n2 => 4,5\n ^".to_string())
+        );
+        assert_eq!(
+            parse_all(parser(), "1 -> 2;3"),
+            Err("Parse error at line 1, column 7 (expected Char):\n1 -> 2;3\n      ^".to_string())
+        );
+
+        let parser = || blocks(lines(number::<u8>));
+        assert_eq!(
+            parse_all(parser(), "1\n2\n\n3\n\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            parse_all(parser(), "1\n2\n\n3\n4x"),
+            Err("Parse error at line 5, column 2 (unexpected input):\n4x\n ^".to_string())
+        );
+        assert_eq!(
+            parse_all(parser(), "1\n\nx\n"),
+            Err("Parse error at line 3, column 1 (expected Digit):\nx\n^".to_string())
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let error = Error::new("x\n", nom::error::ErrorKind::Digit);
+        assert_eq!(
+            error_message("12\n3 äx\n", error),
+            "Parse error at line 2, column 4 (expected Digit):\n3 äx\n   ^"
+        );
+    }
+}
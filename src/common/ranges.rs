@@ -0,0 +1,387 @@
+use std::ops::{Range, RangeInclusive};
+
+use num::PrimInt;
+
+/// A set of values stored as sorted, non-overlapping and non-adjacent half-open ranges.
+///
+/// ```
+/// # use adventofcode::common::ranges::RangeSet;
+/// let mut set = RangeSet::from(2..5);
+/// set.insert(4..8);
+/// set.insert(10..12);
+/// set.remove(6..7);
+/// assert_eq!(set.ranges(), &[2..6, 7..8, 10..12]);
+/// assert_eq!(set.len(), 7);
+/// assert!(set.contains(7));
+/// assert!(!set.contains(8));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// Returns the sorted ranges of the set.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Returns the total number of values in the set.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |len, range| len + (range.end - range.start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// Returns true if the whole `range` is in the set.
+    pub fn contains_range(&self, range: &Range<T>) -> bool {
+        range.is_empty()
+            || self
+                .ranges
+                .iter()
+                .any(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Adds a range to the set, merging it with overlapping and adjacent ranges.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Adds an inclusive range to the set, fails if the range ends at `T::MAX` which can't be the
+    /// end of a half-open range.
+    pub fn try_insert_inclusive(&mut self, range: RangeInclusive<T>) -> Result<(), String> {
+        if range.is_empty() {
+            return Ok(());
+        }
+        let end = range
+            .end()
+            .checked_add(&T::one())
+            .ok_or_else(|| "Range can't include the maximum value".to_string())?;
+        self.insert(*range.start()..end);
+        Ok(())
+    }
+
+    /// Removes a range from the set, splitting ranges that contain it.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first >= last {
+            return;
+        }
+
+        let mut remaining = vec![];
+        if self.ranges[first].start < range.start {
+            remaining.push(self.ranges[first].start..range.start);
+        }
+        if range.end < self.ranges[last - 1].end {
+            remaining.push(range.end..self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, remaining);
+    }
+
+    /// Returns all values that are in both sets.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(range_a), Some(range_b)) = (a.peek(), b.peek()) {
+            let start = range_a.start.max(range_b.start);
+            let end = range_a.end.min(range_b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if range_a.end < range_b.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns all values that are in at least one of the sets.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for range in &other.ranges {
+            union.insert(range.clone());
+        }
+        union
+    }
+
+    /// Returns all values within `bounds` that are not in the set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        let mut complement = Self::from(bounds);
+        for range in &self.ranges {
+            complement.remove(range.clone());
+        }
+        complement
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: PrimInt> TryFrom<RangeInclusive<T>> for RangeSet<T> {
+    type Error = String;
+
+    fn try_from(range: RangeInclusive<T>) -> Result<Self, Self::Error> {
+        let mut set = Self::new();
+        set.try_insert_inclusive(range)?;
+        Ok(set)
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+/// A piecewise mapping of source ranges to destination ranges of the same length.
+/// Values outside of all source ranges are mapped to themselves.
+///
+/// ```
+/// # use adventofcode::common::ranges::{RangeMap, RangeSet};
+/// let mut map = RangeMap::new();
+/// map.insert(98..100, 50);
+/// map.insert(50..98, 52);
+/// assert_eq!(map.map(79), 81);
+/// assert_eq!(map.map(99), 51);
+/// assert_eq!(map.map(10), 10);
+///
+/// let mapped = map.map_set(&RangeSet::from(45..55));
+/// assert_eq!(mapped.ranges(), &[45..50, 52..57]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RangeMap<T> {
+    /// Source ranges with their destination start, sorted by source start.
+    mappings: Vec<(Range<T>, T)>,
+}
+
+impl<T: PrimInt> RangeMap<T> {
+    pub fn new() -> Self {
+        Self { mappings: vec![] }
+    }
+
+    /// Maps the values in `source` to `destination..destination + source.len()`.
+    ///
+    /// Panics if the source range overlaps an already mapped source range.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        let index = self
+            .mappings
+            .partition_point(|(s, _)| s.start < source.start);
+        assert!(
+            self.mappings
+                .get(index)
+                .is_none_or(|(next, _)| source.end <= next.start)
+                && (index == 0 || self.mappings[index - 1].0.end <= source.start),
+            "Overlapping source ranges"
+        );
+        self.mappings.insert(index, (source, destination));
+    }
+
+    /// Maps a single value.
+    pub fn map(&self, value: T) -> T {
+        let index = self.mappings.partition_point(|(s, _)| s.end <= value);
+        match self.mappings.get(index) {
+            Some((source, destination)) if source.start <= value => {
+                *destination + (value - source.start)
+            }
+            _ => value,
+        }
+    }
+
+    /// Maps all ranges of a set, splitting them at the boundaries of the source ranges.
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        let mut mapped = RangeSet::new();
+
+        for range in set.ranges() {
+            let mut start = range.start;
+            let first = self.mappings.partition_point(|(s, _)| s.end <= start);
+
+            for (source, destination) in &self.mappings[first..] {
+                if start >= range.end || source.start >= range.end {
+                    break;
+                }
+                if start < source.start {
+                    // unmapped gap before the source range
+                    mapped.insert(start..source.start);
+                    start = source.start;
+                }
+                let end = range.end.min(source.end);
+                mapped.insert(
+                    *destination + (start - source.start)..*destination + (end - source.start),
+                );
+                start = end;
+            }
+
+            if start < range.end {
+                mapped.insert(start..range.end);
+            }
+        }
+
+        mapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference implementation with a plain bitmap of `0..64`.
+    fn to_bits(set: &RangeSet<u32>) -> u64 {
+        (0..64)
+            .filter(|&v| set.contains(v))
+            .fold(0, |bits, v| bits | 1 << v)
+    }
+
+    fn range_bits(range: &Range<u32>) -> u64 {
+        range.clone().fold(0, |bits, v| bits | 1 << v)
+    }
+
+    #[test]
+    fn test_set_operations_against_bitmap() {
+        let ranges = [
+            0..3,
+            2..6,
+            6..9,
+            10..10,
+            12..20,
+            15..17,
+            19..30,
+            40..41,
+            1..50,
+        ];
+
+        let mut set = RangeSet::new();
+        let mut bits = 0;
+        for (i, range) in ranges.iter().enumerate() {
+            if i % 3 == 2 {
+                set.remove(range.clone());
+                bits &= !range_bits(range);
+            } else {
+                set.insert(range.clone());
+                bits |= range_bits(range);
+            }
+            assert_eq!(to_bits(&set), bits);
+            assert_eq!(set.len(), bits.count_ones());
+            assert!(set.ranges().windows(2).all(|w| w[0].end < w[1].start));
+
+            let other = RangeSet::from_iter([5..13, 18..25]);
+            let other_bits = to_bits(&other);
+            assert_eq!(to_bits(&set.intersect(&other)), bits & other_bits);
+            assert_eq!(to_bits(&set.union(&other)), bits | other_bits);
+            assert_eq!(
+                to_bits(&set.complement(4..60)),
+                !bits & range_bits(&(4..60))
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains_range() {
+        let set = RangeSet::from_iter([2..5, 7..10]);
+        assert!(set.contains_range(&(2..5)));
+        assert!(set.contains_range(&(8..9)));
+        assert!(!set.contains_range(&(4..8)));
+        assert!(set.contains_range(&(20..20)));
+    }
+
+    #[test]
+    fn test_inclusive() {
+        let set = RangeSet::try_from(2..=4u8).unwrap();
+        assert_eq!(set, RangeSet::from(2..5));
+        assert_eq!(set.len(), 3);
+        assert_eq!(RangeSet::try_from(0..=254u8).map(|set| set.len()), Ok(255));
+        assert_eq!(
+            RangeSet::try_from(RangeInclusive::new(255u8, 0)),
+            Ok(RangeSet::new())
+        );
+        assert_eq!(
+            RangeSet::try_from(0..=u8::MAX),
+            Err("Range can't include the maximum value".to_string())
+        );
+
+        let mut set = RangeSet::from(0..10u8);
+        assert!(set.try_insert_inclusive(200..=255).is_err());
+        assert_eq!(set, RangeSet::from(0..10));
+    }
+
+    #[test]
+    fn test_map_seeds_to_locations() {
+        // example from 2023 day 5, as (destination, source, length)
+        let maps: [&[(u64, u64, u64)]; 7] = [
+            &[(50, 98, 2), (52, 50, 48)],
+            &[(0, 15, 37), (37, 52, 2), (39, 0, 15)],
+            &[(49, 53, 8), (0, 11, 42), (42, 0, 7), (57, 7, 4)],
+            &[(88, 18, 7), (18, 25, 70)],
+            &[(45, 77, 23), (81, 45, 19), (68, 64, 13)],
+            &[(0, 69, 1), (1, 0, 69)],
+            &[(60, 56, 37), (56, 93, 4)],
+        ];
+        let maps = maps.map(|mappings| {
+            let mut map = RangeMap::new();
+            for &(destination, source, length) in mappings {
+                map.insert(source..source + length, destination);
+            }
+            map
+        });
+
+        let seeds = [79, 14, 55, 13];
+        let lowest = seeds
+            .iter()
+            .map(|&seed| maps.iter().fold(seed, |value, map| map.map(value)))
+            .min();
+        assert_eq!(lowest, Some(35));
+
+        let seed_ranges = RangeSet::from_iter([79..79 + 14, 55..55 + 13]);
+        let locations = maps
+            .iter()
+            .fold(seed_ranges.clone(), |set, map| map.map_set(&set));
+        assert_eq!(locations.ranges()[0].start, 46);
+        assert_eq!(locations.len(), seed_ranges.len());
+    }
+}
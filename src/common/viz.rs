@@ -0,0 +1,362 @@
+//! Image export of grids as PNG, SVG and animated GIF.
+//!
+//! Images are built with the same closures as the terminal output in [`super::grid`],
+//! but map positions to colors instead of characters. Nothing is written unless an output
+//! directory is set (with `aoc run --viz <dir>`), so solutions can check [`is_enabled`]
+//! before doing extra work for visualisations.
+
+use std::{collections::HashSet, fs, path::PathBuf, sync::Mutex};
+
+use glam::IVec2;
+use itertools::Itertools;
+
+use crate::aoc::output;
+
+pub mod gif;
+pub mod png;
+pub mod svg;
+
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const WHITE: Color = [255, 255, 255];
+pub const GRAY: Color = [128, 128, 128];
+pub const RED: Color = [220, 50, 47];
+pub const GREEN: Color = [0, 200, 0];
+pub const BLUE: Color = [38, 139, 210];
+pub const YELLOW: Color = [255, 215, 0];
+
+static OUTPUT_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Sets the directory for visualisations, `None` disables them.
+pub fn set_output_dir(dir: Option<PathBuf>) {
+    *OUTPUT_DIR.lock().unwrap() = dir;
+}
+
+pub fn is_enabled() -> bool {
+    OUTPUT_DIR.lock().unwrap().is_some()
+}
+
+/// An RGB image.
+///
+/// ```
+/// # use adventofcode::common::viz::{Image, BLACK, RED};
+/// # use glam::IVec2;
+/// let image = Image::from_area((IVec2::new(-1, -1), IVec2::new(1, 1)), |p| {
+///     if p == IVec2::ZERO { RED } else { BLACK }
+/// });
+/// assert_eq!((image.width(), image.height()), (3, 3));
+/// assert_eq!(image.get(1, 1), RED);
+/// assert_eq!(image.scaled(2).width(), 6);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Color>,
+}
+
+impl Image {
+    /// Panics if the image is empty, which can't be encoded.
+    pub fn new(width: usize, height: usize, background: Color) -> Self {
+        assert_size(width, height);
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// Creates an image of an area (inclusive), like [`super::grid::print_area`].
+    /// Panics if the area is empty.
+    pub fn from_area<F>(area: (IVec2, IVec2), map_pos: F) -> Self
+    where
+        F: Fn(IVec2) -> Color,
+    {
+        let size = area.1 - area.0 + 1;
+        assert_size(size.x.max(0) as usize, size.y.max(0) as usize);
+        let pixels = (area.0.y..=area.1.y)
+            .cartesian_product(area.0.x..=area.1.x)
+            .map(|(y, x)| map_pos(IVec2::new(x, y)))
+            .collect();
+        Self {
+            width: size.x as usize,
+            height: size.y as usize,
+            pixels,
+        }
+    }
+
+    /// Creates an image of a set within a range, like [`super::grid::print_set_range`].
+    pub fn from_set(
+        set: &HashSet<IVec2>,
+        range: (IVec2, IVec2),
+        color: Color,
+        background: Color,
+    ) -> Self {
+        Self::from_area(range, |p| if set.contains(&p) { color } else { background })
+    }
+
+    /// Creates an image of a grid, like [`super::grid::print_grid`]. Panics if the grid is empty.
+    pub fn from_grid<T, F>(grid: &[&[T]], map_to_color: F) -> Self
+    where
+        F: Fn(&T) -> Color,
+    {
+        let width = grid.first().map_or(0, |row| row.len());
+        assert_size(width, grid.len());
+        Self {
+            width,
+            height: grid.len(),
+            pixels: grid
+                .iter()
+                .flat_map(|row| row.iter().map(&map_to_color))
+                .collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Color {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Color) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Returns the image with every pixel as a `factor` x `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        assert!(factor > 0, "Scale factor must be at least 1");
+        let pixels = self
+            .pixels
+            .chunks(self.width)
+            .flat_map(|row| {
+                let row = row
+                    .iter()
+                    .flat_map(|&pixel| std::iter::repeat_n(pixel, factor))
+                    .collect_vec();
+                std::iter::repeat_n(row, factor).flatten()
+            })
+            .collect();
+        Self {
+            width: self.width * factor,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        png::encode(self.width as u32, self.height as u32, &self.pixels.concat())
+    }
+
+    /// Encodes the image as SVG, with every pixel as a square of size `scale`.
+    pub fn to_svg(&self, scale: usize) -> String {
+        svg::encode(self.width, self.height, &self.pixels, scale)
+    }
+}
+
+fn assert_size(width: usize, height: usize) {
+    assert!(
+        width > 0 && height > 0,
+        "Image must not be empty, size is {}x{}",
+        width,
+        height
+    );
+}
+
+/// Frames of the same size for an animated GIF.
+///
+/// ```
+/// # use adventofcode::common::viz::{Animation, Image, BLACK, WHITE};
+/// let mut animation = Animation::new(10);
+/// animation.push(Image::new(2, 2, BLACK));
+/// animation.push(Image::new(2, 2, WHITE));
+/// let gif = animation.to_gif().unwrap();
+/// assert!(gif.starts_with(b"GIF89a"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Animation {
+    frames: Vec<Image>,
+    delay: u16,
+}
+
+impl Animation {
+    /// Creates an empty animation showing every frame for `delay` hundredths of a second.
+    pub fn new(delay: u16) -> Self {
+        Self {
+            frames: vec![],
+            delay,
+        }
+    }
+
+    /// Adds a frame, panics if it has a different size than the previous frames.
+    pub fn push(&mut self, frame: Image) {
+        if let Some(first) = self.frames.first() {
+            assert_eq!(
+                (first.width, first.height),
+                (frame.width, frame.height),
+                "All frames need the same size"
+            );
+        }
+        self.frames.push(frame);
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encodes the animation as GIF, fails if the frames use more than 256 colors.
+    pub fn to_gif(&self) -> Result<Vec<u8>, String> {
+        let Some(first) = self.frames.first() else {
+            return Err("Animation without frames".to_string());
+        };
+        if first.width > u16::MAX as usize || first.height > u16::MAX as usize {
+            return Err(format!(
+                "Animation too large: {}x{}",
+                first.width, first.height
+            ));
+        }
+
+        let palette = self
+            .frames
+            .iter()
+            .flat_map(|frame| frame.pixels.iter().copied())
+            .unique()
+            .collect_vec();
+        if palette.len() > 256 {
+            return Err(format!(
+                "Too many colors for GIF: {} (max 256)",
+                palette.len()
+            ));
+        }
+
+        let frames = self
+            .frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .pixels
+                    .iter()
+                    .map(|pixel| palette.iter().position(|c| c == pixel).unwrap() as u8)
+                    .collect_vec()
+            })
+            .collect_vec();
+
+        Ok(gif::encode(
+            first.width as u16,
+            first.height as u16,
+            &palette,
+            &frames,
+            self.delay,
+        ))
+    }
+}
+
+/// Writes an image as `<name>.png` to the output directory, if visualisations are enabled.
+pub fn save_png(name: &str, image: &Image) {
+    if is_enabled() {
+        save(&format!("{}.png", name), Ok(image.to_png()));
+    }
+}
+
+/// Writes an image as `<name>.svg` to the output directory, if visualisations are enabled.
+pub fn save_svg(name: &str, image: &Image, scale: usize) {
+    if is_enabled() {
+        save(
+            &format!("{}.svg", name),
+            Ok(image.to_svg(scale).into_bytes()),
+        );
+    }
+}
+
+/// Writes an animation as `<name>.gif` to the output directory, if visualisations are enabled.
+pub fn save_gif(name: &str, animation: &Animation) {
+    if is_enabled() {
+        save(&format!("{}.gif", name), animation.to_gif());
+    }
+}
+
+fn save(file_name: &str, data: Result<Vec<u8>, String>) {
+    let Some(dir) = OUTPUT_DIR.lock().unwrap().clone() else {
+        return;
+    };
+    let path = dir.join(file_name);
+
+    let result = data.and_then(|data| {
+        fs::create_dir_all(&dir)
+            .and_then(|_| fs::write(&path, data))
+            .map_err(|error| error.to_string())
+    });
+    match result {
+        Ok(()) => output::println_debug(format!("Saved {}", path.display())),
+        Err(error) => output::println(format!(
+            "Error writing visualisation ({}): {}",
+            path.display(),
+            error
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_set_and_scaled() {
+        let set = HashSet::from([IVec2::new(0, 0), IVec2::new(1, 1)]);
+        let image = Image::from_set(&set, (IVec2::ZERO, IVec2::new(1, 1)), WHITE, BLACK);
+        assert_eq!(image.pixels, [WHITE, BLACK, BLACK, WHITE]);
+
+        let scaled = image.scaled(2);
+        assert_eq!((scaled.width(), scaled.height()), (4, 4));
+        assert_eq!(scaled.get(1, 1), WHITE);
+        assert_eq!(scaled.get(2, 1), BLACK);
+        assert_eq!(scaled.get(3, 3), WHITE);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let grid = [[true, false].as_slice(), [false, false].as_slice()];
+        let image = Image::from_grid(&grid, |&b| if b { RED } else { BLACK });
+        assert_eq!(image.pixels, [RED, BLACK, BLACK, BLACK]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Image must not be empty, size is 0x3")]
+    fn test_empty_image() {
+        Image::new(0, 3, BLACK);
+    }
+
+    #[test]
+    #[should_panic(expected = "Image must not be empty, size is 2x0")]
+    fn test_empty_area() {
+        Image::from_area((IVec2::new(0, 1), IVec2::new(1, 0)), |_| BLACK);
+    }
+
+    #[test]
+    fn test_gif_palette() {
+        let mut animation = Animation::new(5);
+        assert!(animation.to_gif().is_err());
+
+        let mut image = Image::new(20, 20, BLACK);
+        for i in 0..256 {
+            image.set(i % 20, i / 20, [i as u8, 1, 2]);
+        }
+        animation.push(image.clone());
+        assert!(animation.to_gif().is_err());
+
+        image.set(0, 0, [1, 1, 2]);
+        animation.frames.clear();
+        animation.push(image);
+        assert!(animation.to_gif().is_ok());
+    }
+}
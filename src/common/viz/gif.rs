@@ -0,0 +1,243 @@
+//! Minimal animated GIF encoder with LZW compression: <https://www.w3.org/Graphics/GIF/spec-gif89a.txt>
+
+use std::collections::HashMap;
+
+const MAX_CODE: u16 = 4095;
+
+/// Encodes frames of palette indices as a looping animated GIF.
+///
+/// The palette can have up to 256 colors, every frame is shown for `delay` hundredths of a second.
+pub fn encode(
+    width: u16,
+    height: u16,
+    palette: &[[u8; 3]],
+    frames: &[Vec<u8>],
+    delay: u16,
+) -> Vec<u8> {
+    assert!(!palette.is_empty() && palette.len() <= 256);
+
+    let palette_bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(1);
+    let min_code_size = palette_bits.max(2) as u8;
+
+    let mut gif = b"GIF89a".to_vec();
+
+    // logical screen descriptor with a global color table
+    gif.extend(width.to_le_bytes());
+    gif.extend(height.to_le_bytes());
+    gif.push(0b1111_0000 | (palette_bits - 1) as u8);
+    gif.extend([0, 0]);
+    for i in 0..1 << palette_bits {
+        gif.extend(palette.get(i).unwrap_or(&[0, 0, 0]));
+    }
+
+    // loop forever
+    gif.extend([0x21, 0xff, 0x0b]);
+    gif.extend(b"NETSCAPE2.0");
+    gif.extend([0x03, 0x01, 0x00, 0x00, 0x00]);
+
+    for frame in frames {
+        assert_eq!(frame.len(), width as usize * height as usize);
+
+        // graphic control extension with the delay, frames replace each other completely
+        gif.extend([0x21, 0xf9, 0x04, 0b0000_0100]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        // image descriptor for the whole screen without a local color table
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend(width.to_le_bytes());
+        gif.extend(height.to_le_bytes());
+        gif.push(0);
+
+        gif.push(min_code_size);
+        for block in lzw_encode(frame, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+    gif
+}
+
+/// Writes codes with a variable number of bits, least significant bit first.
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1 << min_code_size;
+    let end = clear + 1;
+
+    let mut writer = BitWriter {
+        bytes: vec![],
+        buffer: 0,
+        bits: 0,
+    };
+    let mut codes = HashMap::new();
+    let mut next = end + 1;
+    let mut code_size = min_code_size + 1;
+
+    writer.write(clear, code_size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+
+    let mut current = first as u16;
+    for &index in rest {
+        if let Some(&code) = codes.get(&(current, index)) {
+            current = code;
+            continue;
+        }
+
+        writer.write(current, code_size);
+        if next <= MAX_CODE {
+            codes.insert((current, index), next);
+            next += 1;
+            if next > 1 << code_size && code_size < 12 {
+                code_size += 1;
+            }
+        } else {
+            // the code table is full, start over
+            writer.write(clear, code_size);
+            codes.clear();
+            next = end + 1;
+            code_size = min_code_size + 1;
+        }
+        current = index as u16;
+    }
+
+    writer.write(current, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Straightforward LZW decoder to check the encoder.
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+        let initial = (0..clear).map(|i| vec![i as u8]).collect::<Vec<_>>();
+
+        let mut table = initial.clone();
+        let mut code_size = min_code_size + 1;
+        let mut previous: Option<Vec<u8>> = None;
+        let mut output = vec![];
+        let (mut buffer, mut bits, mut pos) = (0u32, 0u8, 0);
+
+        loop {
+            while bits < code_size {
+                buffer |= (bytes[pos] as u32) << bits;
+                pos += 1;
+                bits += 8;
+            }
+            let code = (buffer & ((1 << code_size) - 1)) as u16;
+            buffer >>= code_size;
+            bits -= code_size;
+
+            if code == clear {
+                table = initial.clone();
+                // two placeholders for the clear and end codes
+                table.extend([vec![], vec![]]);
+                code_size = min_code_size + 1;
+                previous = None;
+                continue;
+            }
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code as usize), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = previous.clone();
+                    entry.push(previous[0]);
+                    entry
+                }
+                (None, None) => panic!("Invalid code {}", code),
+            };
+            output.extend(&entry);
+
+            if let Some(previous) = previous {
+                if table.len() <= MAX_CODE as usize {
+                    let mut new_entry = previous;
+                    new_entry.push(entry[0]);
+                    table.push(new_entry);
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        for (colors, len) in [
+            (2, 0),
+            (2, 1),
+            (4, 100),
+            (2, 10_000),
+            (16, 50_000),
+            (256, 100_000),
+        ] {
+            let min_code_size = (usize::BITS - (colors as usize - 1).leading_zeros()).max(2) as u8;
+            // a mix of runs and pseudo random data to fill the code table several times
+            let indices = (0..len)
+                .map(|i: u32| {
+                    if i % 1000 < 500 {
+                        (i / 37 % colors) as u8
+                    } else {
+                        ((i.wrapping_mul(2_654_435_761) >> 13) % colors) as u8
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let encoded = lzw_encode(&indices, min_code_size);
+            assert_eq!(lzw_decode(&encoded, min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn test_encode_structure() {
+        let palette = [[0, 0, 0], [255, 255, 255], [255, 0, 0]];
+        let frames = vec![vec![0, 1, 2, 1], vec![2, 2, 2, 2]];
+        let gif = encode(2, 2, &palette, &frames, 10);
+
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(gif[6..10], [2, 0, 2, 0]);
+        // 3 colors are padded to a color table with 4 entries
+        assert_eq!(gif[10] & 0b111, 1);
+        assert_eq!(gif[13..25], [0, 0, 0, 255, 255, 255, 255, 0, 0, 0, 0, 0]);
+        assert_eq!(gif.last(), Some(&0x3b));
+    }
+}
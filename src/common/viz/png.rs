@@ -0,0 +1,169 @@
+//! Minimal PNG encoder for 8 bit RGB images: <https://www.w3.org/TR/png/>
+//!
+//! The image data is stored uncompressed (deflate "stored" blocks), which keeps the encoder tiny.
+//! Visualisations are small enough that the missing compression doesn't matter.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+const MAX_STORED_BLOCK: usize = 0xffff;
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+/// Encodes RGB pixel data (3 bytes per pixel, row by row) as PNG.
+pub fn encode(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    assert_eq!(rgb.len(), width as usize * height as usize * 3);
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // bit depth 8, color type 2 (RGB), default compression, filter and no interlace
+    header.extend([8, 2, 0, 0, 0]);
+
+    // every scanline starts with its filter type, 0 means no filter
+    let mut raw = Vec::with_capacity(rgb.len() + height as usize);
+    for row in rgb.chunks(width as usize * 3) {
+        raw.push(0);
+        raw.extend(row);
+    }
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+/// Wraps data in a zlib stream with uncompressed deflate blocks.
+pub(super) fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    // deflate with a 32K window and no preset dictionary, the check bits make it divisible by 31
+    let mut zlib = vec![0x78, 0x01];
+
+    let blocks = data.chunks(MAX_STORED_BLOCK).collect::<Vec<_>>();
+    if blocks.is_empty() {
+        zlib.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    for (i, block) in blocks.iter().enumerate() {
+        let last = i == blocks.len() - 1;
+        let len = block.len() as u16;
+        zlib.push(last as u8);
+        zlib.extend(len.to_le_bytes());
+        zlib.extend((!len).to_le_bytes());
+        zlib.extend(*block);
+    }
+
+    zlib.extend(adler32(data).to_be_bytes());
+    zlib
+}
+
+/// CRC-32 as used by PNG (and zip, gzip, ...).
+pub(super) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 == 1 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+}
+
+/// Adler-32 checksum of zlib streams.
+pub(super) fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (a, b) = data.iter().fold((1, 0), |(a, b), &byte| {
+        let a = (a + byte as u32) % MOD;
+        (a, (b + a) % MOD)
+    });
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads the data of a zlib stream that only has stored blocks.
+    fn inflate_stored(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!((zlib[0] as u16 * 256 + zlib[1] as u16) % 31, 0);
+        let mut data = vec![];
+        let mut pos = 2;
+        loop {
+            let header = zlib[pos];
+            let len = u16::from_le_bytes([zlib[pos + 1], zlib[pos + 2]]) as usize;
+            let nlen = u16::from_le_bytes([zlib[pos + 3], zlib[pos + 4]]) as usize;
+            assert_eq!(len, !nlen & 0xffff);
+            data.extend(&zlib[pos + 5..pos + 5 + len]);
+            pos += 5 + len;
+            if header & 1 == 1 {
+                break;
+            }
+        }
+        assert_eq!(&zlib[pos..], adler32(&data).to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(crc32(b"123456789"), 0xcbf43926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn test_zlib_stored_round_trip() {
+        for len in [0, 1, 1000, MAX_STORED_BLOCK, MAX_STORED_BLOCK + 1, 200_000] {
+            let data = (0..len).map(|i| (i * 7 % 251) as u8).collect::<Vec<_>>();
+            assert_eq!(inflate_stored(&zlib_stored(&data)), data);
+        }
+    }
+
+    #[test]
+    fn test_encode() {
+        let rgb = [255, 0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255];
+        let png = encode(2, 2, &rgb);
+        assert_eq!(png[..8], SIGNATURE);
+
+        // walk all chunks and check their CRCs
+        let mut pos = 8;
+        let mut kinds = vec![];
+        let mut idat = vec![];
+        while pos < png.len() {
+            let len = u32::from_be_bytes(png[pos..pos + 4].try_into().unwrap()) as usize;
+            let kind = &png[pos + 4..pos + 8];
+            let crc = u32::from_be_bytes(png[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(&png[pos + 4..pos + 8 + len]));
+            if kind == b"IDAT" {
+                idat.extend(&png[pos + 8..pos + 8 + len]);
+            }
+            kinds.push(String::from_utf8(kind.to_vec()).unwrap());
+            pos += 12 + len;
+        }
+        assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+        assert_eq!(
+            inflate_stored(&idat),
+            [0, 255, 0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 255]
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! SVG output of pixel images, with one rectangle for every horizontal run of the same color.
+
+use std::fmt::Write;
+
+/// Encodes RGB pixels (row by row) as SVG where every pixel is a square of size `scale`.
+pub fn encode(width: usize, height: usize, pixels: &[[u8; 3]], scale: usize) -> String {
+    assert_eq!(pixels.len(), width * height);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">\n",
+        width * scale,
+        height * scale,
+        width,
+        height
+    );
+
+    for (y, row) in pixels.chunks(width.max(1)).enumerate() {
+        let mut x = 0;
+        for run in row.chunk_by(|a, b| a == b) {
+            let [r, g, b] = run[0];
+            writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"#{:02x}{:02x}{:02x}\"/>",
+                x,
+                y,
+                run.len(),
+                r,
+                g,
+                b
+            )
+            .unwrap();
+            x += run.len();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let red = [255, 0, 0];
+        let white = [255, 255, 255];
+        let svg = encode(3, 2, &[red, red, white, white, white, white], 10);
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"20\" viewBox=\"0 0 3 2\""));
+        assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"2\" height=\"1\" fill=\"#ff0000\"/>\n<rect x=\"2\" y=\"0\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>"));
+        assert!(svg.contains("<rect x=\"0\" y=\"1\" width=\"3\" height=\"1\" fill=\"#ffffff\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -43,9 +43,13 @@ macro_rules! aoc_solutions {
                             match format!("day{}", day).as_str() {
                                 $(
                                     stringify!($day) =>
-                                        Day::new(year, day, $year::$day::TITLE, |i| {
-                                            Box::new(<$year::$day::Solution as DayParser>::with_input(i))
-                                        }),
+                                        Day::new(
+                                            year,
+                                            day,
+                                            $year::$day::TITLE,
+                                            <$year::$day::Solution as DayParser>::PARAMS,
+                                            |i, p| Box::new(<$year::$day::Solution as DayParser>::with_params(i, p)),
+                                        ),
                                 )+
                                 _ => panic!("Day {} {} is not implemented yet", day, year),
                             }
@@ -1,5 +1,3 @@
-use std::io::{self, BufRead};
-
 use crate::aoc::day::{DayParser, DaySolution};
 
 pub const TITLE: &str = "TODO";
@@ -7,9 +5,7 @@ pub const TITLE: &str = "TODO";
 pub struct Solution {}
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-
+    fn with_input(input: &str) -> Self {
         todo!();
 
         Self {}
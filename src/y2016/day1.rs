@@ -1,7 +1,4 @@
-use std::{
-    collections::HashSet,
-    io::{self, BufRead},
-};
+use std::collections::HashSet;
 
 use glam::IVec2;
 use nom::{
@@ -14,10 +11,13 @@ use nom::{
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
-    common::grid::{
-        directions::{Direction, Turn},
-        distance::ManhattenDistance,
-        walk::Walk,
+    common::{
+        grid::{
+            directions::{Direction, Turn},
+            distance::ManhattenDistance,
+            walk::Walk,
+        },
+        parsing::parse_input,
     },
 };
 
@@ -34,9 +34,8 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, instructions) = instructions(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let instructions = parse_input(instructions, input);
         Self { instructions }
     }
 }
@@ -103,11 +102,11 @@ mod tests {
 
     #[test]
     fn test_part1_example() {
-        let solution = Solution::with_input(&mut "R2, L3".as_bytes());
+        let solution = Solution::with_input("R2, L3");
         assert_eq!(solution.part1(), "5");
-        let solution = Solution::with_input(&mut "R2, R2, R2".as_bytes());
+        let solution = Solution::with_input("R2, R2, R2");
         assert_eq!(solution.part1(), "2");
-        let solution = Solution::with_input(&mut "R5, L5, R5, R3".as_bytes());
+        let solution = Solution::with_input("R5, L5, R5, R3");
         assert_eq!(solution.part1(), "12");
     }
 
@@ -119,7 +118,7 @@ mod tests {
 
     #[test]
     fn test_part2_example() {
-        let solution = Solution::with_input(&mut "R8, R4, R4, R8".as_bytes());
+        let solution = Solution::with_input("R8, R4, R4, R8");
         assert_eq!(solution.part2(), "4");
     }
 
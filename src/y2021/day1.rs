@@ -1,5 +1,3 @@
-use std::io::BufRead;
-
 use crate::{
     aoc::day::{DayParser, DaySolution},
     common::parsing::parse_lines_vec,
@@ -12,7 +10,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         Self {
             depths: parse_lines_vec(input),
         }
@@ -1,5 +1,3 @@
-use std::io::{self, BufRead};
-
 use crate::aoc::day::{DayParser, DaySolution};
 
 pub const TITLE: &str = "Calorie Counting";
@@ -9,8 +7,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
+    fn with_input(input: &str) -> Self {
         let elves = input
             .split("\n\n")
             .map(|elf| elf.lines().map(|c| c.parse::<u32>().unwrap()));
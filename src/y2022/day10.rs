@@ -1,17 +1,13 @@
-use std::io::{self, BufRead};
-
 use nom::{
-    branch::alt,
-    bytes::complete::tag,
-    character::complete::{self, newline},
-    multi::separated_list1,
-    sequence::preceded,
-    IResult, Parser,
+    branch::alt, bytes::complete::tag, character::complete, sequence::preceded, IResult, Parser,
 };
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
-    common::ocr,
+    common::{
+        ocr,
+        parsing::{lines, parse_input},
+    },
 };
 
 pub const TITLE: &str = "Cathode-Ray Tube";
@@ -30,22 +26,18 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, instructions) = instructions(&input).unwrap();
+    fn with_input(input: &str) -> Self {
+        let instructions = parse_input(lines(instruction), input);
 
         Self { instructions }
     }
 }
 
-fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    separated_list1(
-        newline,
-        alt((
-            tag("noop").map(|_| Instruction::Noop),
-            preceded(tag("addx "), complete::i8).map(Instruction::Add),
-        )),
-    )(input)
+fn instruction(input: &str) -> IResult<&str, Instruction> {
+    alt((
+        tag("noop").map(|_| Instruction::Noop),
+        preceded(tag("addx "), complete::i8).map(Instruction::Add),
+    ))(input)
 }
 
 impl DaySolution for Solution {
@@ -77,7 +69,7 @@ impl DaySolution for Solution {
         let pixels = self.get_pixels();
         let screen = pixels.chunks(LINE_LENGTH as usize).collect::<Vec<_>>();
         ocr::print_image(&screen);
-        ocr::read_message(&screen)
+        ocr::read_message(&screen).unwrap_or_else(|error| panic!("{}", error))
     }
 }
 
@@ -1,17 +1,17 @@
-use std::{
-    cmp::Ordering,
-    io::{self, BufRead},
-};
+use std::cmp::Ordering;
 
 use nom::{
     branch::alt,
-    character::complete::{self, char, newline},
-    multi::{many1, separated_list0, separated_list1},
+    character::complete::{self, char},
+    multi::separated_list0,
     sequence::delimited,
     IResult, Parser,
 };
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::parsing::{blocks, lines, parse_input},
+};
 
 pub const TITLE: &str = "Distress Signal";
 
@@ -42,18 +42,16 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, packets) = packets(&input).unwrap();
+    fn with_input(input: &str) -> Self {
+        let packets = parse_input(blocks(lines(packet)), input)
+            .into_iter()
+            .flatten()
+            .collect();
 
         Self { packets }
     }
 }
 
-fn packets(input: &str) -> IResult<&str, Vec<Packet>> {
-    separated_list1(many1(newline), packet)(input)
-}
-
 fn packet(input: &str) -> IResult<&str, Packet> {
     alt((
         delimited(char('['), separated_list0(char(','), packet), char(']')).map(Packet::List),
@@ -1,24 +1,28 @@
 use std::{
     collections::{BTreeSet, HashMap},
     hash::Hash,
-    io::{self, BufRead},
 };
 
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, alpha1, newline},
+    character::complete::{self, alpha1},
     multi::separated_list1,
     sequence::{preceded, tuple},
     IResult,
 };
-use pathfinding::prelude::bfs;
 
 #[cfg(feature = "parallel")]
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::{
+        graph::all_pairs_distances,
+        parsing::{lines, parse_input},
+    },
+};
 
 pub const TITLE: &str = "Proboscidea Volcanium";
 
@@ -42,9 +46,8 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, valves) = valves(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let valves = parse_input(lines(valve), input);
 
         let tunnels = valves
             .iter()
@@ -62,14 +65,10 @@ impl DayParser for Solution {
             .collect::<Vec<u16>>();
         valves_to_visit.push(START);
 
-        let mut valve_distances = HashMap::new();
-        valves_to_visit.into_iter().combinations(2).for_each(|v| {
-            if let &[from, to] = v.as_slice() {
-                let distance = get_distance(from, to, &tunnels);
-                valve_distances.insert((from, to), distance);
-                valve_distances.insert((to, from), distance);
-            }
-        });
+        let valve_distances = all_pairs_distances(&valves_to_visit, |valve| tunnels[valve].clone())
+            .into_iter()
+            .map(|(valves, distance)| (valves, distance as u8))
+            .collect();
 
         let valves_with_flow = valves_with_flow
             .iter()
@@ -83,10 +82,6 @@ impl DayParser for Solution {
     }
 }
 
-fn valves(input: &str) -> IResult<&str, Vec<Valve>> {
-    separated_list1(newline, valve)(input)
-}
-
 fn valve(input: &str) -> IResult<&str, Valve> {
     let (input, (id, flow_rate, tunnels)) = tuple((
         preceded(tag("Valve "), valve_id),
@@ -209,15 +204,6 @@ impl State {
     }
 }
 
-fn get_distance(from: u16, to: u16, tunnels: &HashMap<u16, Vec<u16>>) -> u8 {
-    let path = bfs(
-        &from,
-        |&valve| tunnels.get(&valve).unwrap().clone(),
-        |&valve| valve == to,
-    );
-    path.unwrap().len() as u8 - 1 // path includes the start, but the start doesn't add to the distance
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
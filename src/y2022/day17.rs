@@ -1,14 +1,17 @@
-use std::{
-    collections::HashSet,
-    io::{self, BufRead},
-};
-
 use glam::IVec2;
-use itertools::Itertools;
 
 use crate::{
-    aoc::day::{DayParser, DaySolution},
-    common::grid::{directions::Direction, parse_set, walk::Walk},
+    aoc::{
+        day::{DayParser, DaySolution},
+        params::{valid, Param, Params},
+    },
+    common::{
+        animation,
+        bitgrid::BitGrid,
+        cycle::find_cycle,
+        grid::{directions::Direction, minmax::minmax_ivec2, parse_set, walk::Walk},
+        viz::{self, Animation, Image, BLACK, GRAY, WHITE, YELLOW},
+    },
 };
 
 pub const TITLE: &str = "Pyroclastic Flow";
@@ -31,167 +34,260 @@ const ROCKS: &str = "####
 ##
 ##";
 
-const ROCKS_P1: u16 = 2022;
-const ROCKS_P2: u64 = 1_000_000_000_000;
+const CHAMBER_WIDTH: usize = 7;
+const ANIMATION_ROWS: i32 = 30; // rows below the falling rock to show
+const VIZ_ROCKS: usize = 20; // rocks to animate step by step
+const VIZ_SCALE: usize = 8;
 
+/// The chamber and the rocks are bit grids with the lowest line first, so the chamber can grow.
 pub struct Solution {
-    rocks: Vec<Vec<IVec2>>,
+    rocks: Vec<BitGrid>,
     jets: Vec<Direction>,
+    rocks_part1: usize,
+    rocks_part2: u64,
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rocks_part1",
+            default: "2022",
+            example: None,
+            description: "Number of falling rocks in part 1",
+            validate: valid::<usize>,
+        },
+        Param {
+            name: "rocks_part2",
+            default: "1000000000000",
+            example: None,
+            description: "Number of falling rocks in part 2",
+            validate: valid::<u64>,
+        },
+    ];
+
+    fn with_input(input: &str) -> Self {
+        Self::with_params(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn with_params(input: &str, params: &Params) -> Self {
         let rocks = ROCKS
             .split("\n\n")
             .map(|rock_str| {
-                let rock = parse_set(rock_str.lines().map(|l| l.to_string()));
-                rock.iter()
-                    .map(|r| {
-                        r.move_distance(Direction::Up, rock.iter().max_by_key(|r| r.y).unwrap().y)
-                            .move_distance(Direction::Right, 2)
-                    })
-                    .collect()
+                let rock = parse_set(rock_str.lines());
+                let (_, max) = minmax_ivec2(rock.iter());
+                BitGrid::from_positions(
+                    max.x as usize + 1,
+                    max.y as usize + 1,
+                    rock.iter().map(|r| IVec2::new(r.x, max.y - r.y)),
+                )
             })
             .collect();
 
-        let jets = io::read_to_string(input)
-            .unwrap()
+        let jets = input
             .trim()
             .chars()
-            .map(|c| c.into())
+            .map(|c| Direction::try_from(c).unwrap())
             .collect();
 
-        Self { rocks, jets }
+        Self {
+            rocks,
+            jets,
+            rocks_part1: params.get("rocks_part1"),
+            rocks_part2: params.get("rocks_part2"),
+        }
     }
 }
 
 impl DaySolution for Solution {
     fn part1(&self) -> String {
-        let mut chamber = (0..7).map(|x| IVec2::new(x, 1)).collect::<HashSet<_>>();
+        let mut chamber = BitGrid::new(CHAMBER_WIDTH, 0);
         let mut direction_offset = 0;
 
-        for r in 0..ROCKS_P1 {
+        for r in 0..self.rocks_part1 {
             direction_offset = self.fall_rock(
                 &mut chamber,
-                &self.rocks[r as usize % self.rocks.len()],
+                &self.rocks[r % self.rocks.len()],
                 direction_offset,
+                animate_chamber,
             );
         }
 
-        chamber_height(&chamber).to_string()
+        if viz::is_enabled() {
+            self.save_visualisations(&chamber);
+        }
+
+        chamber.height().to_string()
     }
 
     fn part2(&self) -> String {
-        let mut chamber = (0..7).map(|x| IVec2::new(x, 1)).collect::<HashSet<_>>();
+        let mut chamber = BitGrid::new(CHAMBER_WIDTH, 0);
         let mut rock_offset = 0;
         let mut direction_offset = 0;
 
-        let mut states = vec![(vec![0; 7], rock_offset, direction_offset)];
-        let mut heights = vec![0];
-
-        let loop_state = loop {
-            direction_offset =
-                self.fall_rock(&mut chamber, &self.rocks[rock_offset], direction_offset);
+        let cycle = find_cycle(((vec![0; 7], rock_offset, direction_offset), 0), || {
+            direction_offset = self.fall_rock(
+                &mut chamber,
+                &self.rocks[rock_offset],
+                direction_offset,
+                |_, _| {},
+            );
             rock_offset = (rock_offset + 1) % self.rocks.len();
 
             let state = (top_line(&chamber), rock_offset, direction_offset);
-            if states.contains(&state) {
-                break state;
-            } else {
-                states.push(state);
-                heights.push(chamber_height(&chamber));
-            }
-        };
-
-        let loop_start = states.iter().position(|s| s == &loop_state).unwrap();
-        let loop_size = states.len() - loop_start;
-
-        let after_loop_height = chamber_height(&chamber);
-        let loop_height = after_loop_height - heights[loop_start];
-
-        let top_line_after_loop = loop_state.0;
-        let top_line_offset = top_line_after_loop.iter().min().unwrap();
-        let mut end_chamber = top_line_after_loop
-            .iter()
-            .enumerate()
-            .map(|(x, y)| IVec2::new(x as i32, *y - top_line_offset + 1))
-            .collect::<HashSet<_>>();
-
-        let remaining_rocks = ROCKS_P2 - states.len() as u64;
-        for _ in 0..(remaining_rocks % loop_size as u64) {
-            direction_offset =
-                self.fall_rock(&mut end_chamber, &self.rocks[rock_offset], direction_offset);
-            rock_offset = (rock_offset + 1) % self.rocks.len();
-        }
+            (state, chamber.height() as i32)
+        });
 
-        (after_loop_height as u64
-            + remaining_rocks / loop_size as u64 * loop_height as u64
-            + chamber_height(&end_chamber) as u64)
-            .to_string()
+        cycle.extrapolate(self.rocks_part2).to_string()
     }
 }
 
 impl Solution {
-    fn fall_rock(
+    /// Lets a rock fall until it lands, `on_move` is called with every position of the rock.
+    fn fall_rock<F>(
         &self,
-        chamber: &mut HashSet<IVec2>,
-        rock: &[IVec2],
+        chamber: &mut BitGrid,
+        rock: &BitGrid,
         mut direction_offset: usize,
-    ) -> usize {
-        let mut rock = rock
-            .iter()
-            .map(|r| r.move_distance(Direction::Up, chamber_height(chamber) + 3))
-            .collect::<Vec<_>>();
+        mut on_move: F,
+    ) -> usize
+    where
+        F: FnMut(&BitGrid, &[IVec2]),
+    {
+        let fits = |pos: IVec2| {
+            pos.x >= 0
+                && pos.y >= 0
+                && pos.x as usize + rock.width() <= CHAMBER_WIDTH
+                && !chamber.overlaps_at(rock, pos)
+        };
+        let mut pos = IVec2::new(2, chamber.height() as i32 + 3);
 
         loop {
-            let moved_rock = rock
-                .iter()
-                .map(|r| r.move_step(self.jets[direction_offset]))
-                .collect::<Vec<_>>();
-            if !moved_rock
-                .iter()
-                .any(|r| r.x < 0 || r.x > 6 || chamber.contains(r))
-            {
-                rock = moved_rock;
+            if animation::is_enabled() || viz::is_enabled() {
+                on_move(chamber, &rock_positions(rock, pos));
+            }
+            let moved = pos.move_step(self.jets[direction_offset]);
+            if fits(moved) {
+                pos = moved;
             }
             direction_offset = (direction_offset + 1) % self.jets.len();
 
-            let fallen_rock = rock
-                .iter()
-                .map(|r| r.move_step(Direction::Down))
-                .collect::<Vec<_>>();
-
-            if fallen_rock.iter().any(|r| chamber.contains(r)) {
-                chamber.extend(rock);
-                break direction_offset;
+            // lines are counted from the floor
+            let fallen = pos - IVec2::Y;
+            if fits(fallen) {
+                pos = fallen;
             } else {
-                rock = fallen_rock;
+                let height = chamber.height().max(pos.y as usize + rock.height());
+                chamber.resize(height);
+                chamber.insert_grid(rock, pos);
+                break direction_offset;
             }
         }
     }
+
+    /// Saves an animation of the first falling rocks and the whole tower as PNG
+    fn save_visualisations(&self, tower: &BitGrid) {
+        let mut chamber = BitGrid::new(CHAMBER_WIDTH, 0);
+        let mut direction_offset = 0;
+        let mut frames = vec![];
+
+        for r in 0..VIZ_ROCKS {
+            direction_offset = self.fall_rock(
+                &mut chamber,
+                &self.rocks[r % self.rocks.len()],
+                direction_offset,
+                |chamber, rock| frames.push((chamber.clone(), rock.to_vec())),
+            );
+        }
+
+        let top = frames
+            .iter()
+            .flat_map(|(_, rock)| rock.iter().map(|r| r.y))
+            .min()
+            .unwrap_or(0);
+        let mut animation = Animation::new(5);
+        for (chamber, rock) in &frames {
+            animation.push(chamber_image(chamber, rock, top).scaled(VIZ_SCALE));
+        }
+        viz::save_gif("rocks", &animation);
+
+        let top = 1 - tower.height() as i32;
+        viz::save_png(
+            "tower",
+            &chamber_image(tower, &[], top).scaled(VIZ_SCALE / 2),
+        );
+    }
 }
 
-fn chamber_height(chamber: &HashSet<IVec2>) -> i32 {
-    -(chamber.iter().min_by_key(|r| r.y).unwrap().y - 1)
+/// Positions of a rock for drawing, with the y axis pointing down and the floor at y = 1.
+fn rock_positions(rock: &BitGrid, pos: IVec2) -> Vec<IVec2> {
+    rock.iter()
+        .map(|r| {
+            let r = r + pos;
+            IVec2::new(r.x, -r.y)
+        })
+        .collect()
 }
 
-fn top_line(chamber: &HashSet<IVec2>) -> Vec<i32> {
-    let top_line = chamber
-        .iter()
-        .into_grouping_map_by(|r| r.x)
-        .min_by_key(|_, r| r.y)
-        .iter()
-        .sorted_by_key(|(x, _)| *x)
-        .map(|(_, r)| r.y)
-        .collect::<Vec<_>>();
-    let offset = top_line.iter().max().unwrap();
-    top_line.iter().map(|y| y - offset).collect()
+/// Returns true if the chamber contains the position in drawing coordinates, see [`rock_positions`].
+fn chamber_contains(chamber: &BitGrid, p: IVec2) -> bool {
+    chamber.contains(IVec2::new(p.x, -p.y))
+}
+
+fn animate_chamber(chamber: &BitGrid, rock: &[IVec2]) {
+    animation::frame(|| {
+        let top = rock.iter().map(|r| r.y).min().unwrap() - 1;
+        let bottom = (top + ANIMATION_ROWS).min(1);
+        (top..=bottom)
+            .map(|y| {
+                (-1..=7)
+                    .map(|x| {
+                        let p = IVec2::new(x, y);
+                        match () {
+                            _ if x == -1 || x == 7 => '|',
+                            _ if y == 1 => '-',
+                            _ if rock.contains(&p) => '@',
+                            _ if chamber_contains(chamber, p) => '#',
+                            _ => '.',
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    });
+}
+
+fn chamber_image(chamber: &BitGrid, rock: &[IVec2], top: i32) -> Image {
+    Image::from_area((IVec2::new(0, top), IVec2::new(6, 1)), |p| {
+        if p.y == 1 {
+            GRAY
+        } else if rock.contains(&p) {
+            YELLOW
+        } else if chamber_contains(chamber, p) {
+            WHITE
+        } else {
+            BLACK
+        }
+    })
+}
+
+/// Heights of the columns relative to the highest one.
+fn top_line(chamber: &BitGrid) -> Vec<i32> {
+    let height = chamber.height() as i32;
+    (0..CHAMBER_WIDTH as i32)
+        .map(|x| {
+            let column_height = (0..height)
+                .rev()
+                .find(|&y| chamber.contains(IVec2::new(x, y)))
+                .map_or(0, |y| y + 1);
+            column_height - height
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::input;
+    use crate::{input, params};
 
     #[test]
     fn test_part1_example() {
@@ -199,6 +295,12 @@ mod tests {
         assert_eq!(solution.part1(), "3068");
     }
 
+    #[test]
+    fn test_part1_example_ten_rocks() {
+        let solution = Solution::with_params(input!(example), &params!(rocks_part1 = "10"));
+        assert_eq!(solution.part1(), "17");
+    }
+
     #[test]
     fn test_part1_input() {
         let solution = Solution::with_input(input!(input));
@@ -1,27 +1,25 @@
-use std::{collections::HashSet, io::BufRead};
+use std::collections::HashSet;
 
 use glam::IVec2;
-use itertools::Itertools;
-
-#[cfg(feature = "parallel")]
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
     common::{
+        animation,
+        bitgrid::BitGrid,
         grid::{
-            directions::{
-                Directions, BOTTOM_LEFT, BOTTOM_RIGHT, DOWN, LEFT, RIGHT, TOP_LEFT, TOP_RIGHT, UP,
-            },
+            directions::{BOTTOM_LEFT, BOTTOM_RIGHT, DOWN, LEFT, RIGHT, TOP_LEFT, TOP_RIGHT, UP},
             minmax::minmax_ivec2,
             parse_set,
         },
         parsing::lines_iter,
+        viz::{self, Animation, Image, BLACK, GREEN},
     },
 };
 
 pub const TITLE: &str = "Unstable Diffusion";
 
+/// Positions to check for every direction, the second one is the step.
 const DIRECTIONS: [[IVec2; 3]; 4] = [
     [TOP_LEFT, UP, TOP_RIGHT],
     [BOTTOM_LEFT, DOWN, BOTTOM_RIGHT],
@@ -29,34 +27,55 @@ const DIRECTIONS: [[IVec2; 3]; 4] = [
     [TOP_RIGHT, RIGHT, BOTTOM_RIGHT],
 ];
 
+/// Free space around the elves, the grid grows when they reach the border.
+const MARGIN: usize = 16;
+
 pub struct Solution {
-    elves: HashSet<IVec2>,
+    elves: BitGrid,
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let elves = parse_set(lines_iter(input));
-        Self { elves }
+    fn with_input(input: &str) -> Self {
+        let positions = parse_set(lines_iter(input));
+        let (_, max) = minmax_ivec2(positions.iter());
+        let elves = BitGrid::from_positions(max.x as usize + 1, max.y as usize + 1, positions);
+        Self {
+            elves: elves.expanded(MARGIN),
+        }
     }
 }
 
 impl DaySolution for Solution {
     fn part1(&self) -> String {
         let mut elves = self.elves.clone();
+        let mut origin = IVec2::ZERO;
 
         for round in 0..10 {
-            move_round(&mut elves, round);
+            move_round(&mut elves, &mut origin, round);
         }
 
-        let (min, max) = minmax_ivec2(elves.iter());
+        let (min, max) = elves.bounding_box().unwrap();
         let size = (max.x - min.x + 1) * (max.y - min.y + 1);
         (size - elves.len() as i32).to_string()
     }
 
     fn part2(&self) -> String {
         let mut elves = self.elves.clone();
+        let mut origin = IVec2::ZERO;
+        let mut frames = vec![];
         for round in 0.. {
-            if move_round(&mut elves, round) == 0 {
+            if viz::is_enabled() {
+                frames.push(elves.iter().map(|elf| elf - origin).collect());
+            }
+            if animation::is_enabled() {
+                animation::frame_area_with_title(
+                    format!("Round {}", round + 1),
+                    elves.bounding_box().unwrap(),
+                    |p| if elves.contains(p) { "#" } else { "." }.to_string(),
+                );
+            }
+            if move_round(&mut elves, &mut origin, round) == 0 {
+                save_animation(&frames);
                 return (round + 1).to_string();
             }
         }
@@ -64,45 +83,63 @@ impl DaySolution for Solution {
     }
 }
 
-fn move_round(elves: &mut HashSet<IVec2>, round: u16) -> u16 {
-    #[cfg(feature = "parallel")]
-    let elves_iter = elves.par_iter();
-    #[cfg(not(feature = "parallel"))]
-    let elves_iter = elves.iter();
+/// Moves all elves at the same time with bit operations on the whole grid, returns the number of
+/// moved elves. `origin` is moved when the grid grows.
+fn move_round(elves: &mut BitGrid, origin: &mut IVec2, round: usize) -> usize {
+    let (min, max) = elves.bounding_box().unwrap();
+    if min.cmple(IVec2::ZERO).any()
+        || max.x as usize + 1 >= elves.width()
+        || max.y as usize + 1 >= elves.height()
+    {
+        *elves = elves.expanded(MARGIN);
+        *origin += IVec2::splat(MARGIN as i32);
+    }
 
-    let preferred_targets = elves_iter
-        .filter_map(|elf| get_prefered_target(elves, elf, round).map(|target| (*elf, target)))
-        .collect::<Vec<(IVec2, IVec2)>>();
+    // positions where the direction is free of elves
+    let free = DIRECTIONS.map(|checks| {
+        let blocked = checks.iter().fold(
+            BitGrid::new(elves.width(), elves.height()),
+            |blocked, &dir| &blocked | &elves.shifted(-dir),
+        );
+        !&blocked
+    });
+
+    let mut undecided = &*elves & &elves.any_neighbors();
+    let mut targets: [BitGrid; 4] = Default::default();
+    for offset in 0..4 {
+        let i = (round + offset) % 4;
+        let proposing = &undecided & &free[i];
+        undecided &= &!&free[i];
+        targets[i] = proposing.shifted(DIRECTIONS[i][1]);
+    }
 
+    // only elves from opposite directions can propose the same position
     let mut moves = 0;
-    preferred_targets
-        .iter()
-        .into_group_map_by(|(_, target)| *target)
-        .into_iter()
-        .for_each(|(target, from)| {
-            if from.len() == 1 {
-                elves.remove(&from[0].0);
-                elves.insert(target);
-                moves += 1;
-            }
-        });
+    for (i, opposite) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+        let target = &targets[i] & &!&targets[opposite];
+        *elves ^= &target.shifted(-DIRECTIONS[i][1]);
+        *elves |= &target;
+        moves += target.len();
+    }
     moves
 }
 
-fn get_prefered_target(elves: &HashSet<IVec2>, elf: &IVec2, round: u16) -> Option<IVec2> {
-    if elf.neighbors().iter().all(|n| !elves.contains(n)) {
-        None
-    } else {
-        (0..4).find_map(|offset| {
-            let direction = DIRECTIONS[(round + offset) as usize % 4];
-            let checks = direction.map(|d| *elf + d);
-            if checks.iter().all(|n| !elves.contains(n)) {
-                Some(checks[1])
-            } else {
-                None
-            }
-        })
+/// Saves the elves of every round as animation and the final positions as PNG
+fn save_animation(frames: &[HashSet<IVec2>]) {
+    let Some(last) = frames.last() else {
+        return;
+    };
+    let bounds = minmax_ivec2(frames.iter().flatten());
+
+    let mut animation = Animation::new(5);
+    for elves in frames {
+        animation.push(Image::from_set(elves, bounds, GREEN, BLACK));
     }
+    viz::save_gif("elves", &animation);
+    viz::save_png(
+        "elves",
+        &Image::from_set(last, bounds, GREEN, BLACK).scaled(4),
+    );
 }
 
 #[cfg(test)]
@@ -1,18 +1,15 @@
-use std::{
-    collections::{HashMap, HashSet},
-    io::BufRead,
-};
+use std::collections::HashMap;
 
 use glam::IVec2;
 use num::integer::lcm;
-use pathfinding::prelude::astar;
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
     common::{
+        animation,
+        bitgrid::BitGrid,
         grid::{
-            directions::{Direction, Directions},
-            distance::ManhattenDistance,
+            directions::{Direction, DIRECTIONS},
             minmax::minmax_ivec2,
             parse_map,
         },
@@ -38,31 +35,31 @@ struct Blizzard {
 pub struct Solution {
     start: IVec2,
     end: IVec2,
-    blocked_at_times: Vec<HashSet<IVec2>>,
+    blocked_at_times: Vec<BitGrid>,
+    area: (IVec2, IVec2),
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         let map = parse_map(lines_iter(input), |c| match c {
             '.' => Tile::Free,
             '#' => Tile::Wall,
-            d @ ('^' | '>' | 'v' | '<') => Tile::Blizzard(d.into()),
+            d @ ('^' | '>' | 'v' | '<') => Tile::Blizzard(d.try_into().unwrap()),
             _ => panic!("Invalid tile: {}", c),
         });
         let (min, max) = minmax_ivec2(map.keys());
         let start = free_in_line(&map, &min.y);
         let end = free_in_line(&map, &max.y);
 
-        let mut walls = map
-            .iter()
-            .filter_map(|(pos, tile)| match tile {
-                Tile::Wall => Some(pos),
+        let (width, height) = (max.x as usize + 1, max.y as usize + 1);
+        let walls = BitGrid::from_positions(
+            width,
+            height,
+            map.iter().filter_map(|(pos, tile)| match tile {
+                Tile::Wall => Some(*pos),
                 _ => None,
-            })
-            .cloned()
-            .collect::<HashSet<_>>();
-        walls.insert(start.up());
-        walls.insert(end.down());
+            }),
+        );
 
         let blizzards = map
             .into_iter()
@@ -109,9 +106,9 @@ impl DayParser for Solution {
                 Some(current)
             })
             .map(|blizzards| {
-                let mut blizzards = blizzards.into_iter().map(|b| b.pos).collect::<HashSet<_>>();
-                blizzards.extend(&walls);
-                blizzards
+                let blizzards =
+                    BitGrid::from_positions(width, height, blizzards.into_iter().map(|b| b.pos));
+                &blizzards | &walls
             })
             .collect::<Vec<_>>();
 
@@ -119,6 +116,7 @@ impl DayParser for Solution {
             start,
             end,
             blocked_at_times,
+            area: (min, max),
         }
     }
 }
@@ -135,59 +133,67 @@ fn free_in_line(map: &HashMap<IVec2, Tile>, line: &i32) -> IVec2 {
 
 impl DaySolution for Solution {
     fn part1(&self) -> String {
-        let start = State::new(self.start);
-        self.find_path(&start, &self.end).0.to_string()
+        self.find_path(self.start, self.end, 0).to_string()
     }
 
     fn part2(&self) -> String {
-        let start = State::new(self.start);
-        let (dist1, state) = self.find_path(&start, &self.end);
-        let (dist2, state) = self.find_path(&state, &self.start);
-        let (dist3, _) = self.find_path(&state, &self.end);
-        (dist1 + dist2 + dist3).to_string()
+        let time = self.find_path(self.start, self.end, 0);
+        let time = self.find_path(self.end, self.start, time);
+        self.find_path(self.start, self.end, time).to_string()
     }
 }
 
 impl Solution {
-    fn find_path(&self, start: &State, end: &IVec2) -> (u32, State) {
-        let res = astar(
-            start,
-            |state| state.next(&self.blocked_at_times),
-            |state| state.pos.manhattan_distance(end),
-            |state| &state.pos == end,
-        )
-        .expect("No path found");
-        (res.1, res.0.last().unwrap().clone())
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct State {
-    pos: IVec2,
-    time: usize,
-}
+    /// Returns the time when `end` is reached, all reachable positions are tracked at the same time.
+    /// Panics if the positions repeat after a full blizzard period without reaching `end`.
+    fn find_path(&self, start: IVec2, end: IVec2, mut time: usize) -> usize {
+        let period = self.blocked_at_times.len();
+        let (min, max) = self.area;
+        let mut reachable = BitGrid::from_positions(
+            (max.x - min.x + 1) as usize,
+            (max.y - min.y + 1) as usize,
+            [start],
+        );
+
+        let mut period_start = reachable.clone();
+        let mut steps = 0;
+        while !reachable.contains(end) {
+            if steps == period {
+                if reachable == period_start {
+                    panic!("No path found");
+                }
+                period_start = reachable.clone();
+                steps = 0;
+            }
+            steps += 1;
+            time += 1;
+            let blocked = &self.blocked_at_times[time % self.blocked_at_times.len()];
+            // waiting or moving in any direction, as long as the position isn't blocked
+            let moved = DIRECTIONS.iter().fold(reachable.clone(), |moved, &dir| {
+                &moved | &reachable.shifted(dir)
+            });
+            reachable = &moved & &!blocked;
+            self.animate(&reachable, blocked);
+        }
 
-impl State {
-    fn new(pos: IVec2) -> Self {
-        Self { pos, time: 0 }
+        time
     }
 
-    fn next(&self, blocked_at_times: &[HashSet<IVec2>]) -> Vec<(Self, u32)> {
-        let next_time = (self.time + 1) % blocked_at_times.len();
-        let mut next = self.pos.directions();
-        next.push(self.pos);
-        next.retain(|pos| !blocked_at_times[next_time].contains(pos));
-        next.into_iter()
-            .map(|pos| {
-                (
-                    Self {
-                        pos,
-                        time: next_time,
-                    },
-                    1,
-                )
-            })
-            .collect()
+    fn animate(&self, reachable: &BitGrid, blocked: &BitGrid) {
+        let (min, max) = self.area;
+        animation::frame_area((min, max), |p| {
+            let wall = p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y;
+            if reachable.contains(p) {
+                "E"
+            } else if !blocked.contains(p) {
+                "."
+            } else if wall {
+                "#"
+            } else {
+                "*"
+            }
+            .to_string()
+        });
     }
 }
 
@@ -196,6 +202,13 @@ mod tests {
     use super::*;
     use crate::input;
 
+    #[test]
+    #[should_panic(expected = "No path found")]
+    fn test_no_path() {
+        let solution = Solution::with_input("#.###\n#.#.#\n###.#\n");
+        solution.part1();
+    }
+
     #[test]
     fn test_part1_example() {
         let solution = Solution::with_input(input!(example));
@@ -1,5 +1,3 @@
-use std::io::BufRead;
-
 use crate::{
     aoc::day::{DayParser, DaySolution},
     common::parsing::lines_iter,
@@ -12,8 +10,8 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let numbers = lines_iter(input).map(|l| parse_snafu(&l)).collect();
+    fn with_input(input: &str) -> Self {
+        let numbers = lines_iter(input).map(parse_snafu).collect();
         Self { numbers }
     }
 }
@@ -1,8 +1,6 @@
-use std::io::BufRead;
-
 use crate::{
     aoc::day::{DayParser, DaySolution},
-    common::parsing::lines_vec,
+    common::parsing::lines_iter,
 };
 
 pub const TITLE: &str = "Rucksack Reorganization";
@@ -12,9 +10,9 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         Self {
-            lines: lines_vec(input),
+            lines: lines_iter(input).map(String::from).collect(),
         }
     }
 }
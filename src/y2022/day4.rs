@@ -1,35 +1,33 @@
-use std::{
-    io::{self, BufRead},
-    ops::RangeInclusive,
-};
-
 use nom::{
-    character::complete::{self, char, newline},
-    multi::separated_list1,
+    character::complete::{self, char},
+    combinator::map_res,
     sequence::separated_pair,
     IResult,
 };
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::{
+        parsing::{lines, parse_input},
+        ranges::RangeSet,
+    },
+};
 
 pub const TITLE: &str = "Camp Cleanup";
 
 struct Pair {
-    range_a: RangeInclusive<u32>,
-    range_b: RangeInclusive<u32>,
+    range_a: RangeSet<u32>,
+    range_b: RangeSet<u32>,
 }
 
 impl Pair {
     fn includes(&self) -> bool {
-        let a_contains_b = self.range_a.contains(self.range_b.start())
-            && self.range_a.contains(self.range_b.end());
-        let b_contains_a = self.range_b.contains(self.range_a.start())
-            && self.range_b.contains(self.range_a.end());
-        a_contains_b || b_contains_a
+        let union = self.range_a.union(&self.range_b);
+        union == self.range_a || union == self.range_b
     }
 
     fn overlaps(&self) -> bool {
-        self.range_a.contains(self.range_b.start()) || self.range_b.contains(self.range_a.start())
+        !self.range_a.intersect(&self.range_b).is_empty()
     }
 }
 
@@ -38,25 +36,22 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, pairs) = pairs(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let pairs = parse_input(lines(pair), input);
         Self { pairs }
     }
 }
 
-fn pairs(input: &str) -> IResult<&str, Vec<Pair>> {
-    separated_list1(newline, pair)(input)
-}
-
 fn pair(input: &str) -> IResult<&str, Pair> {
     let (input, (range_a, range_b)) = separated_pair(range, char(','), range)(input)?;
     Ok((input, Pair { range_a, range_b }))
 }
 
-fn range(input: &str) -> IResult<&str, RangeInclusive<u32>> {
-    let (input, (start, end)) = separated_pair(complete::u32, char('-'), complete::u32)(input)?;
-    Ok((input, start..=end))
+fn range(input: &str) -> IResult<&str, RangeSet<u32>> {
+    map_res(
+        separated_pair(complete::u32, char('-'), complete::u32),
+        |(start, end)| RangeSet::try_from(start..=end),
+    )(input)
 }
 
 impl DaySolution for Solution {
@@ -1,7 +1,4 @@
-use std::{
-    collections::HashSet,
-    io::{self, BufRead},
-};
+use std::collections::HashSet;
 
 use crate::aoc::day::{DayParser, DaySolution};
 
@@ -12,11 +9,8 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let datastream = io::read_to_string(input)
-            .unwrap()
-            .chars()
-            .collect::<Vec<char>>();
+    fn with_input(input: &str) -> Self {
+        let datastream = input.chars().collect::<Vec<char>>();
 
         Self { datastream }
     }
@@ -60,10 +54,10 @@ mod tests {
 
     #[test]
     fn test_part1_more_examples() {
-        assert_eq!(Solution::with_input(&mut EX_1.as_bytes()).part1(), "5");
-        assert_eq!(Solution::with_input(&mut EX_2.as_bytes()).part1(), "6");
-        assert_eq!(Solution::with_input(&mut EX_3.as_bytes()).part1(), "10");
-        assert_eq!(Solution::with_input(&mut EX_4.as_bytes()).part1(), "11");
+        assert_eq!(Solution::with_input(EX_1).part1(), "5");
+        assert_eq!(Solution::with_input(EX_2).part1(), "6");
+        assert_eq!(Solution::with_input(EX_3).part1(), "10");
+        assert_eq!(Solution::with_input(EX_4).part1(), "11");
     }
 
     #[test]
@@ -80,10 +74,10 @@ mod tests {
 
     #[test]
     fn test_part2_more_examples() {
-        assert_eq!(Solution::with_input(&mut EX_1.as_bytes()).part2(), "23");
-        assert_eq!(Solution::with_input(&mut EX_2.as_bytes()).part2(), "23");
-        assert_eq!(Solution::with_input(&mut EX_3.as_bytes()).part2(), "29");
-        assert_eq!(Solution::with_input(&mut EX_4.as_bytes()).part2(), "26");
+        assert_eq!(Solution::with_input(EX_1).part2(), "23");
+        assert_eq!(Solution::with_input(EX_2).part2(), "23");
+        assert_eq!(Solution::with_input(EX_3).part2(), "29");
+        assert_eq!(Solution::with_input(EX_4).part2(), "26");
     }
 
     #[test]
@@ -1,10 +1,13 @@
-use std::{collections::HashSet, io::BufRead};
+use std::collections::HashSet;
 
 use glam::IVec2;
 use itertools::Itertools;
 
 use crate::{
-    aoc::day::{DayParser, DaySolution},
+    aoc::{
+        day::{DayParser, DaySolution},
+        params::{valid, Param, Params},
+    },
     common::{
         grid::{distance::ManhattenDistance, minmax::minmax_ivec2, parse_set},
         parsing::lines_iter,
@@ -17,10 +20,23 @@ pub struct Solution {
     galaxies: HashSet<IVec2>,
     empty_columns: Vec<i32>,
     empty_rows: Vec<i32>,
+    expansion: u64,
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    const PARAMS: &'static [Param] = &[Param {
+        name: "expansion",
+        default: "1000000",
+        example: None,
+        description: "How many times larger empty rows and columns are in part 2",
+        validate: valid_expansion,
+    }];
+
+    fn with_input(input: &str) -> Self {
+        Self::with_params(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn with_params(input: &str, params: &Params) -> Self {
         let galaxies = parse_set(lines_iter(input));
         let (_, max) = minmax_ivec2(galaxies.iter());
         let empty_columns = (0..=max.x)
@@ -34,6 +50,7 @@ impl DayParser for Solution {
             galaxies,
             empty_columns,
             empty_rows,
+            expansion: params.get("expansion"),
         }
     }
 }
@@ -44,7 +61,7 @@ impl DaySolution for Solution {
     }
 
     fn part2(&self) -> String {
-        self.expanded_distances(999999).to_string()
+        self.expanded_distances(self.expansion - 1).to_string()
     }
 }
 
@@ -62,6 +79,14 @@ impl Solution {
     }
 }
 
+/// An empty row or column has to stay at least one row or column.
+fn valid_expansion(value: &str) -> Result<(), String> {
+    match value.parse::<u64>() {
+        Ok(0) => Err("must be at least 1".to_string()),
+        _ => valid::<u64>(value),
+    }
+}
+
 fn count_empty_in_range(empty: &[i32], a: &i32, b: &i32) -> u64 {
     let range = if a < b { a..=b } else { b..=a };
     empty.iter().filter(|e| range.contains(e)).count() as u64
@@ -70,7 +95,7 @@ fn count_empty_in_range(empty: &[i32], a: &i32, b: &i32) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::input;
+    use crate::{input, params};
 
     #[test]
     fn test_part1_example() {
@@ -86,9 +111,11 @@ mod tests {
 
     #[test]
     fn test_part2_example() {
-        let solution = Solution::with_input(input!(example));
-        assert_eq!(solution.expanded_distances(9), 1030);
-        assert_eq!(solution.expanded_distances(99), 8410);
+        let solution = Solution::with_params(input!(example), &params!(expansion = "10"));
+        assert_eq!(solution.part2(), "1030");
+        let solution = Solution::with_params(input!(example), &params!(expansion = "100"));
+        assert_eq!(solution.part2(), "8410");
+        assert!(Params::new(Solution::PARAMS, &[("expansion", "0")]).is_err());
     }
 
     #[test]
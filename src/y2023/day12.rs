@@ -1,5 +1,3 @@
-use std::{collections::HashMap, io::BufRead};
-
 use itertools::Itertools;
 
 #[cfg(feature = "parallel")]
@@ -9,7 +7,7 @@ use std::slice::Iter;
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
-    common::parsing::lines_iter,
+    common::{memo::Memo, parsing::lines_iter},
 };
 
 pub const TITLE: &str = "Hot Springs";
@@ -32,7 +30,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         let records = lines_iter(input)
             .map(|line| {
                 let (springs, damaged_groups) = line.split_once(' ').unwrap();
@@ -92,7 +90,7 @@ impl Solution {
 
 impl Record {
     fn valid_arrangements(&self) -> u64 {
-        valid_arrangements_cached(&mut HashMap::new(), &self.springs, &self.damaged_groups)
+        valid_arrangements(&mut Memo::new(), &self.springs, &self.damaged_groups)
     }
 
     fn unfold(&self, times: usize) -> Record {
@@ -112,64 +110,60 @@ impl Record {
     }
 }
 
-fn valid_arrangements_cached(
-    cache: &mut HashMap<(usize, usize), u64>,
+/// The memo key is the number of remaining springs and groups, which identifies the suffixes.
+fn valid_arrangements(
+    memo: &mut Memo<(usize, usize), u64>,
     springs: &[Condition],
     damaged_groups: &[usize],
 ) -> u64 {
-    cache
-        .get(&(springs.len(), damaged_groups.len()))
-        .copied()
-        .unwrap_or_else(|| {
-            let count = match (springs.split_first(), damaged_groups.split_first()) {
-                (_, None) if springs.contains(&Condition::Damaged) => 0, // invalid if still damaged springs, but no groups left
-                (_, None) => 1, // valid if no groups left, and no damaged springs left
-                (None, Some(_)) => 0, // invalid if no springs left but still groups required
-                (Some((Condition::Operational, tail)), _) => {
-                    // skip operational springs
-                    valid_arrangements_cached(cache, tail, damaged_groups)
-                }
-                (Some((Condition::Damaged, _)), Some((current_group, remaining_groups))) => {
-                    if &springs.len() >= current_group {
-                        // try to consume all damaged springs in the current group
-                        let (group, tail) = springs.split_at(*current_group);
-                        if group.contains(&Condition::Operational) {
-                            0 // invalid if there are still operational springs in the group
-                        } else {
-                            match tail.split_first() {
-                                None => {
-                                    if remaining_groups.is_empty() {
-                                        1 // valid if no more springs left, and no more groups required
-                                    } else {
-                                        0 // invalid if no more springs left, but still groups required
-                                    }
-                                }
-                                Some((Condition::Damaged, _)) => 0, // invalid if the next spring is also damaged
-                                Some((_, tail)) => {
-                                    // otherwise skip one spring and continue
-                                    valid_arrangements_cached(cache, tail, remaining_groups)
+    memo.get_or_compute((springs.len(), damaged_groups.len()), |memo| {
+        match (springs.split_first(), damaged_groups.split_first()) {
+            (_, None) if springs.contains(&Condition::Damaged) => 0, // invalid if still damaged springs, but no groups left
+            (_, None) => 1, // valid if no groups left, and no damaged springs left
+            (None, Some(_)) => 0, // invalid if no springs left but still groups required
+            (Some((Condition::Operational, tail)), _) => {
+                // skip operational springs
+                valid_arrangements(memo, tail, damaged_groups)
+            }
+            (Some((Condition::Damaged, _)), Some((current_group, remaining_groups))) => {
+                if &springs.len() >= current_group {
+                    // try to consume all damaged springs in the current group
+                    let (group, tail) = springs.split_at(*current_group);
+                    if group.contains(&Condition::Operational) {
+                        0 // invalid if there are still operational springs in the group
+                    } else {
+                        match tail.split_first() {
+                            None => {
+                                if remaining_groups.is_empty() {
+                                    1 // valid if no more springs left, and no more groups required
+                                } else {
+                                    0 // invalid if no more springs left, but still groups required
                                 }
                             }
+                            Some((Condition::Damaged, _)) => 0, // invalid if the next spring is also damaged
+                            Some((_, tail)) => {
+                                // otherwise skip one spring and continue
+                                valid_arrangements(memo, tail, remaining_groups)
+                            }
                         }
-                    } else {
-                        0 // invalid if there are not enough springs left
                     }
+                } else {
+                    0 // invalid if there are not enough springs left
                 }
-                (Some((Condition::Unknown, tail)), _) => {
-                    let mut springs = tail.to_vec();
+            }
+            (Some((Condition::Unknown, tail)), _) => {
+                let mut springs = tail.to_vec();
 
-                    // adding an operational spring is not needed, as they are skipped anyway
-                    let operational = valid_arrangements_cached(cache, &springs, damaged_groups);
+                // adding an operational spring is not needed, as they are skipped anyway
+                let operational = valid_arrangements(memo, &springs, damaged_groups);
 
-                    springs.insert(0, Condition::Damaged);
-                    let damaged = valid_arrangements_cached(cache, &springs, damaged_groups);
+                springs.insert(0, Condition::Damaged);
+                let damaged = valid_arrangements(memo, &springs, damaged_groups);
 
-                    operational + damaged
-                }
-            };
-            cache.insert((springs.len(), damaged_groups.len()), count);
-            count
-        })
+                operational + damaged
+            }
+        }
+    })
 }
 
 #[cfg(test)]
@@ -1,13 +1,10 @@
-use std::{
-    io::{self, BufRead},
-    ops::RangeInclusive,
-};
+use std::ops::RangeInclusive;
 
 use itertools::Itertools;
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
-    common::grid::parse_vec,
+    common::{grid::parse_vec, parsing::split_blocks},
 };
 
 pub const TITLE: &str = "Point of Incidence";
@@ -17,11 +14,9 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let patterns = input
-            .split("\n\n")
-            .map(|pattern: &str| parse_vec(pattern.lines().map(|l| l.to_string()), |c| c == '#'))
+    fn with_input(input: &str) -> Self {
+        let patterns = split_blocks(input)
+            .map(|pattern: &str| parse_vec(pattern.lines(), |c| c == '#'))
             .collect_vec();
 
         Self { patterns }
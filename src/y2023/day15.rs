@@ -1,7 +1,4 @@
-use std::{
-    array::from_fn,
-    io::{self, BufRead},
-};
+use std::array::from_fn;
 
 use itertools::Itertools;
 
@@ -14,8 +11,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
+    fn with_input(input: &str) -> Self {
         let sequence = input.trim().split(',').map(|s| s.to_string()).collect_vec();
         Self { sequence }
     }
@@ -1,9 +1,7 @@
-use std::{collections::HashMap, io::BufRead};
+use std::collections::HashMap;
 
-use colored::Colorize;
 use glam::IVec2;
 use itertools::Itertools;
-use pathfinding::prelude::astar;
 
 use crate::{
     aoc::{
@@ -11,11 +9,12 @@ use crate::{
         output,
     },
     common::{
+        graph::{astar, astar_traced, Trace},
         grid::{
             directions::{Direction, Turn},
             distance::ManhattenDistance,
             minmax::minmax_ivec2,
-            parse_map, print_area,
+            parse_map,
             walk::Walk,
         },
         parsing::lines_iter,
@@ -43,7 +42,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         let map = parse_map(lines_iter(input), |c| c.to_digit(10).unwrap() as u8);
         let (_, target) = minmax_ivec2(map.keys());
         Self { map, target }
@@ -62,37 +61,38 @@ impl DaySolution for Solution {
 
 impl Solution {
     fn find_path(&self, min_steps: u8, max_steps: u8) -> u32 {
-        let path = astar(
-            &START,
-            |state| state.next(&self.map, min_steps, max_steps),
-            |state| state.pos.manhattan_distance(&self.target),
-            |state| state.pos == self.target && state.steps >= min_steps,
-        )
-        .expect("No path found");
+        let successors = |state: &State| state.next(&self.map, min_steps, max_steps);
+        let heuristic = |state: &State| state.pos.manhattan_distance(&self.target);
+        let success = |state: &State| state.pos == self.target && state.steps >= min_steps;
 
         if output::is_debug_enabled() {
-            self.print_path(&path.0);
+            let mut trace = Trace::default();
+            let (_, cost) = astar_traced(&START, successors, heuristic, success, &mut trace)
+                .expect("No path found");
+            self.print_path(&trace);
+            cost
+        } else {
+            astar(&START, successors, heuristic, success)
+                .expect("No path found")
+                .1
         }
-
-        path.1
     }
 
-    fn print_path(&self, path: &[State]) {
-        let path_direction = path
+    fn print_path(&self, trace: &Trace<State>) {
+        let path_direction = trace
+            .path
             .iter()
             .map(|state| (state.pos, state.dir))
             .collect::<HashMap<IVec2, Direction>>();
 
-        print_area((IVec2::ZERO, self.target), |pos| {
-            match path_direction.get(&pos) {
-                Some(Direction::Up) => "^".red(),
-                Some(Direction::Right) => ">".red(),
-                Some(Direction::Down) => "v".red(),
-                Some(Direction::Left) => "<".red(),
-                None => self.map.get(&pos).unwrap().to_string().normal(),
-            }
-            .to_string()
-        });
+        trace.print_on_grid(
+            (IVec2::ZERO, self.target),
+            |state| state.pos,
+            |pos| match path_direction.get(&pos) {
+                Some(dir) => dir.to_string(),
+                None => self.map.get(&pos).unwrap().to_string(),
+            },
+        );
     }
 }
 
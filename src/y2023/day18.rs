@@ -1,19 +1,19 @@
 use core::panic;
-use std::io::{self, BufRead};
 
-use glam::IVec2;
-use itertools::Itertools;
 use nom::{
     bytes::complete::{tag, take},
-    character::complete::{self, anychar, newline, space1},
-    multi::separated_list1,
+    character::complete::{self, anychar, space1},
+    combinator::map_res,
     sequence::{delimited, preceded, separated_pair, tuple},
     IResult, Parser,
 };
 
 use crate::{
     aoc::day::{DayParser, DaySolution},
-    common::grid::{area::shoelace_area, directions::Direction, walk::Walk},
+    common::{
+        grid::{directions::Direction, polygon::Polygon},
+        parsing::{lines, parse_input},
+    },
 };
 
 pub const TITLE: &str = "Lavaduct Lagoon";
@@ -30,29 +30,15 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, instructions) = instructions(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let instructions = parse_input(lines(instruction), input);
         Self { instructions }
     }
 }
 
-fn instructions(input: &str) -> IResult<&str, Vec<Instruction>> {
-    separated_list1(newline, instruction)(input)
-}
-
 fn instruction(input: &str) -> IResult<&str, Instruction> {
-    let (input, (dir, dist)) = separated_pair(
-        anychar.map(|char| match char {
-            'U' => Direction::Up,
-            'D' => Direction::Down,
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            _ => panic!("Invalid direction"),
-        }),
-        space1,
-        complete::i32,
-    )(input)?;
+    let (input, (dir, dist)) =
+        separated_pair(map_res(anychar, Direction::try_from), space1, complete::i32)(input)?;
     let (input, (real_dir, real_dist)) =
         delimited(tag(" ("), color_instruction, complete::char(')'))(input)?;
 
@@ -86,37 +72,15 @@ fn color_instruction(input: &str) -> IResult<&str, (Direction, i32)> {
 
 impl DaySolution for Solution {
     fn part1(&self) -> String {
-        let (perimeter, length) = self.get_perimeter(|i| i.dir, |i| i.dist);
-        (shoelace_area(&perimeter) + (length / 2) + 1).to_string()
+        Polygon::from_moves(self.instructions.iter().map(|i| (i.dir, i.dist)))
+            .lattice_points()
+            .to_string()
     }
 
     fn part2(&self) -> String {
-        let (perimeter, length) = self.get_perimeter(|i| i.real_dir, |i| i.real_dist);
-        (shoelace_area(&perimeter) + (length / 2) + 1).to_string()
-    }
-}
-
-impl Solution {
-    fn get_perimeter(
-        &self,
-        dir_fn: fn(&Instruction) -> Direction,
-        dist_fn: fn(&Instruction) -> i32,
-    ) -> (Vec<IVec2>, i64) {
-        let mut current_pos = IVec2::ZERO;
-        let mut perimeter_length = 0;
-
-        let perimeter_points = self
-            .instructions
-            .iter()
-            .map(|instruction| {
-                let dir = dir_fn(instruction);
-                let dist = dist_fn(instruction);
-                current_pos = current_pos.move_distance(dir, dist);
-                perimeter_length += dist;
-                current_pos
-            })
-            .collect_vec();
-        (perimeter_points, perimeter_length as i64)
+        Polygon::from_moves(self.instructions.iter().map(|i| (i.real_dir, i.real_dist)))
+            .lattice_points()
+            .to_string()
     }
 }
 
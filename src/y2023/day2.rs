@@ -1,16 +1,17 @@
-use std::io::{self, BufRead};
-
 use itertools::Itertools;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, newline},
+    character::complete,
     multi::separated_list1,
     sequence::{delimited, terminated, tuple},
     IResult, Parser,
 };
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::parsing::{lines, parse_input},
+};
 
 pub const TITLE: &str = "Cube Conundrum";
 
@@ -39,17 +40,12 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, games) = games(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let games = parse_input(lines(game), input);
         Self { games }
     }
 }
 
-fn games(input: &str) -> IResult<&str, Vec<Game>> {
-    separated_list1(newline, game)(input)
-}
-
 fn game(input: &str) -> IResult<&str, Game> {
     let (input, (id, cubes)) = tuple((
         delimited(tag("Game "), complete::u8, tag(": ")),
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, io::BufRead};
+use std::collections::HashMap;
 
 use glam::IVec2;
 use itertools::Itertools;
@@ -30,7 +30,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         let engine = parse_map(lines_iter(input), |c| match c {
             '.' => Tile::Empty,
             digit if digit.is_ascii_digit() => Tile::Number(digit.to_digit(10).unwrap() as u8),
@@ -105,8 +105,8 @@ impl DaySolution for Solution {
             .filter(|part_number| part_number.part == '*')
             .into_grouping_map_by(|&part_number| part_number.part_pos)
             .collect::<Vec<_>>()
-            .into_iter()
-            .filter_map(|(_pos, part_numbers)| {
+            .into_values()
+            .filter_map(|part_numbers| {
                 if part_numbers.len() == 2 {
                     Some(part_numbers[0].number * part_numbers[1].number)
                 } else {
@@ -1,17 +1,16 @@
-use std::{
-    collections::BTreeSet,
-    io::{self, BufRead},
-};
+use std::collections::BTreeSet;
 
 use nom::{
     bytes::complete::tag,
-    character::complete::{self, newline, space1},
-    multi::separated_list1,
+    character::complete::{self, space1},
     sequence::{delimited, separated_pair, tuple},
     IResult,
 };
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::parsing::{lines, numbers, parse_input},
+};
 
 pub const TITLE: &str = "Scratchcards";
 
@@ -27,25 +26,23 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, cards) = cards(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let cards = parse_input(lines(card), input);
         Self { cards }
     }
 }
 
-fn cards(input: &str) -> IResult<&str, Vec<Card>> {
-    separated_list1(newline, card)(input)
-}
-
 fn card(input: &str) -> IResult<&str, Card> {
     let (input, id) = delimited(
         tuple((tag("Card"), space1)),
         complete::u16,
         tuple((tag(":"), space1)),
     )(input)?;
-    let (input, (winning_numbers, numbers)) =
-        separated_pair(numbers, tuple((space1, tag("|"), space1)), numbers)(input)?;
+    let (input, (winning_numbers, numbers)) = separated_pair(
+        numbers(space1),
+        tuple((space1, tag("|"), space1)),
+        numbers(space1),
+    )(input)?;
     Ok((
         input,
         Card {
@@ -56,10 +53,6 @@ fn card(input: &str) -> IResult<&str, Card> {
     ))
 }
 
-fn numbers(input: &str) -> IResult<&str, Vec<u8>> {
-    separated_list1(space1, complete::u8)(input)
-}
-
 impl DaySolution for Solution {
     fn part1(&self) -> String {
         self.cards
@@ -1,5 +1,3 @@
-use std::io::{self, BufRead};
-
 use nom::{
     bytes::complete::tag,
     character::complete::{digit1, newline, space1},
@@ -8,7 +6,10 @@ use nom::{
     IResult, Parser,
 };
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::parsing::parse_input,
+};
 
 pub const TITLE: &str = "Wait For It";
 
@@ -18,9 +19,8 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, (time, distance)) = parse(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let (time, distance) = parse_input(parse, input);
         Self { time, distance }
     }
 }
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, io::BufRead};
+use std::collections::HashMap;
 
 use itertools::{Itertools, Position};
 
@@ -34,7 +34,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         let hands = lines_iter(input)
             .map(|line| {
                 let (cards, bid) = line.split_once(' ').unwrap();
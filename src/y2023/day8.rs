@@ -1,19 +1,22 @@
-use std::{
-    collections::HashMap,
-    io::{self, BufRead},
-};
+use std::collections::HashMap;
 
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{self, multispace1, newline},
-    multi::{fold_many1, many1},
+    character::complete::{self, multispace1},
+    combinator::map,
+    multi::many1,
     sequence::{preceded, separated_pair, terminated},
     IResult, Parser,
 };
-use num::integer::lcm;
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::{
+        math::combine_cycles,
+        parsing::{key_values, parse_input},
+    },
+};
 
 pub const TITLE: &str = "Haunted Wasteland";
 
@@ -33,9 +36,8 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, (instructions, network)) = parser(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let (instructions, network) = parse_input(parser, input);
         Self {
             instructions,
             network,
@@ -55,16 +57,10 @@ fn instructions(input: &str) -> IResult<&str, Vec<Direction>> {
 }
 
 fn network(input: &str) -> IResult<&str, HashMap<String, Branch>> {
-    fold_many1(
-        terminated(
-            separated_pair(complete::alphanumeric1, tag(" = "), branch),
-            newline,
-        ),
-        HashMap::new,
-        |mut map, (key, branch)| {
-            map.insert(key.to_string(), branch);
-            map
-        },
+    key_values(
+        map(complete::alphanumeric1, String::from),
+        tag(" = "),
+        branch,
     )(input)
 }
 
@@ -85,26 +81,37 @@ fn branch(input: &str) -> IResult<&str, Branch> {
 
 impl DaySolution for Solution {
     fn part1(&self) -> String {
-        self.find_target("AAA", |node| node == "ZZZ").to_string()
+        self.find_target("AAA", 0, |node| node == "ZZZ")
+            .0
+            .to_string()
     }
 
     fn part2(&self) -> String {
-        self.network
+        let cycles = self
+            .network
             .keys()
             .filter(|node| node.ends_with('A'))
-            .map(|node| self.find_target(node, |node| node.ends_with('Z')))
-            .reduce(lcm)
-            .unwrap()
-            .to_string()
+            .map(|node| {
+                let is_target = |node: &str| node.ends_with('Z');
+                let (offset, target) = self.find_target(node, 0, is_target);
+                let (period, _) = self.find_target(target, offset, is_target);
+                (offset as i64, period as i64)
+            })
+            .collect::<Vec<_>>();
+
+        combine_cycles(&cycles).unwrap().to_string()
     }
 }
 
 impl Solution {
-    fn find_target(&self, start: &str, is_target: fn(&str) -> bool) -> u64 {
+    /// Returns the number of steps to the first target and the target node,
+    /// starting at `start` with the instruction at `step`.
+    fn find_target(&self, start: &str, step: u64, is_target: fn(&str) -> bool) -> (u64, &str) {
         let mut current_node = start;
         self.instructions
             .iter()
             .cycle()
+            .skip(step as usize % self.instructions.len())
             .enumerate()
             .find_map(|(dist, instr)| {
                 let next_node = match instr {
@@ -112,7 +119,7 @@ impl Solution {
                     Direction::Right => &self.network[current_node].right,
                 };
                 if is_target(next_node) {
-                    Some(dist as u64 + 1)
+                    Some((dist as u64 + 1, next_node.as_str()))
                 } else {
                     current_node = next_node;
                     None
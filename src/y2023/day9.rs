@@ -1,4 +1,4 @@
-use std::{io::BufRead, iter};
+use std::iter;
 
 use itertools::Itertools;
 
@@ -14,7 +14,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
+    fn with_input(input: &str) -> Self {
         let chains = lines_iter(input)
             .map(|line| {
                 line.split(' ')
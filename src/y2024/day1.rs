@@ -1,14 +1,10 @@
-use std::io::{self, BufRead};
-
 use itertools::Itertools;
-use nom::{
-    character::complete::{self, newline, space1},
-    multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
-};
+use nom::{character::complete::space1, sequence::separated_pair};
 
-use crate::aoc::day::{DayParser, DaySolution};
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::parsing::{lines, number, parse_input},
+};
 
 pub const TITLE: &str = "Historian Hysteria";
 
@@ -18,24 +14,17 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, (left, right)) = lists(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let (left, right) = parse_input(
+            lines(separated_pair(number::<u32>, space1, number::<u32>)),
+            input,
+        )
+        .into_iter()
+        .unzip();
         Self { left, right }
     }
 }
 
-fn lists(input: &str) -> IResult<&str, (Vec<u32>, Vec<u32>)> {
-    let (input, pairs) = separated_list1(newline, pair)(input)?;
-    let (left, right) = pairs.into_iter().unzip();
-    Ok((input, (left, right)))
-}
-
-fn pair(input: &str) -> IResult<&str, (u32, u32)> {
-    let (input, (a, b)) = separated_pair(complete::u32, space1, complete::u32)(input)?;
-    Ok((input, (a, b)))
-}
-
 impl DaySolution for Solution {
     fn part1(&self) -> String {
         let diffs = self
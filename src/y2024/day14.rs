@@ -1,29 +1,35 @@
-use std::{
-    collections::HashMap,
-    io::{self, BufRead},
-};
+use std::collections::HashMap;
 
 use glam::IVec2;
 use itertools::Itertools;
 use nom::{
-    character::complete::{self, char, newline},
-    multi::separated_list1,
-    sequence::separated_pair,
+    bytes::complete::tag,
+    character::complete::char,
+    sequence::{preceded, separated_pair},
     IResult,
 };
 
 use crate::{
-    aoc::day::{DayParser, DaySolution},
-    common::grid::print_area,
+    aoc::{
+        day::{DayParser, DaySolution},
+        params::{valid_size, Param, Params},
+    },
+    common::{
+        animation,
+        grid::print_area,
+        math::crt,
+        parsing::{ivec2, lines, parse_input},
+        viz::{self, Animation, Image, BLACK, GREEN},
+    },
 };
 
 pub const TITLE: &str = "Restroom Redoubt";
 
-pub const EXAMPLE_SIZE: IVec2 = IVec2 { x: 11, y: 7 };
-pub const INPUT_SIZE: IVec2 = IVec2 { x: 101, y: 103 };
-
 pub const OUTLIER_THRESHOLD: usize = 30; // how many robots on the same row/col
 
+const VIZ_SECONDS: i32 = 30; // seconds to animate before the tree appears
+const VIZ_SCALE: usize = 4;
+
 #[derive(Debug, Clone)]
 struct Robot {
     position: IVec2,
@@ -36,39 +42,35 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, robots) = robots(&input).unwrap();
+    const PARAMS: &'static [Param] = &[Param {
+        name: "size",
+        default: "101x103",
+        example: Some("11x7"),
+        description: "Size of the area",
+        validate: valid_size,
+    }];
+
+    fn with_input(input: &str) -> Self {
+        Self::with_params(input, &Params::defaults(Self::PARAMS))
+    }
 
-        let max_x = robots.iter().map(|r| r.position.x).max().unwrap();
-        let size = if max_x <= EXAMPLE_SIZE.x {
-            EXAMPLE_SIZE
-        } else {
-            INPUT_SIZE
-        };
+    fn with_params(input: &str, params: &Params) -> Self {
+        let robots = parse_input(lines(robot), input);
+        let size = params.ivec2("size");
 
         Self { robots, size }
     }
 }
 
-fn robots(input: &str) -> IResult<&str, Vec<Robot>> {
-    separated_list1(newline, robot)(input)
-}
-
 fn robot(input: &str) -> IResult<&str, Robot> {
-    let (input, (position, velocity)) = separated_pair(ivec2('p'), char(' '), ivec2('v'))(input)?;
+    let (input, (position, velocity)) = separated_pair(
+        preceded(tag("p="), ivec2),
+        char(' '),
+        preceded(tag("v="), ivec2),
+    )(input)?;
     Ok((input, Robot { position, velocity }))
 }
 
-fn ivec2(c: char) -> impl Fn(&str) -> IResult<&str, IVec2> {
-    move |input: &str| {
-        let (input, _) = char(c)(input)?;
-        let (input, _) = char('=')(input)?;
-        let (input, (x, y)) = separated_pair(complete::i32, char(','), complete::i32)(input)?;
-        Ok((input, IVec2 { x, y }))
-    }
-}
-
 impl DaySolution for Solution {
     fn part1(&self) -> String {
         let mut robots = self.robots.clone();
@@ -92,27 +94,38 @@ impl DaySolution for Solution {
     }
 
     fn part2(&self) -> String {
-        let mut robots = self.robots.clone();
-
-        let x_outlier = find_first_outlier(&robots, self.size, |p| p.x);
+        // the tree is visible when both the columns and the rows have an outlier,
+        // which repeat every `size.x` and `size.y` seconds respectively
+        let x_outlier = find_first_outlier(&self.robots, self.size, |p| p.x, self.size.x)
+            .expect("No tree found");
+        let y_outlier = find_first_outlier(&self.robots, self.size, |p| p.y, self.size.y)
+            .expect("No tree found");
+
+        let (second, _) = crt(&[
+            (x_outlier as i64, self.size.x as i64),
+            (y_outlier as i64, self.size.y as i64),
+        ])
+        .expect("No tree found");
 
+        let mut robots = self.robots.clone();
         for robot in &mut robots {
-            robot.steps(x_outlier, self.size);
+            robot.steps(second as i32, self.size);
         }
+        print_map(&robots, self.size);
 
-        let mut second = x_outlier;
-
-        loop {
-            second += self.size.x;
-            for robot in &mut robots {
-                robot.steps(self.size.x, self.size);
-            }
-
-            if robots_count(&robots).values().all_equal_value() == Ok(&1) {
-                print_map(&robots, self.size);
-                return second.to_string();
-            }
+        if animation::is_enabled() {
+            animate_outliers(
+                &self.robots,
+                self.size,
+                (x_outlier, y_outlier),
+                second as i32,
+            );
+        }
+        if viz::is_enabled() {
+            save_visualisations(&self.robots, self.size, second as i32);
         }
+
+        second.to_string()
     }
 }
 
@@ -134,21 +147,23 @@ impl Robot {
     }
 }
 
-fn find_first_outlier(robots: &[Robot], size: IVec2, map_fn: fn(&IVec2) -> i32) -> i32 {
+/// Returns the first second with an outlier, the values of `map_fn` repeat after `period` seconds.
+fn find_first_outlier(
+    robots: &[Robot],
+    size: IVec2,
+    map_fn: fn(&IVec2) -> i32,
+    period: i32,
+) -> Option<i32> {
     let mut robots = robots.to_vec();
-    let mut second = 0;
 
-    loop {
-        second += 1;
+    (1..=period).find(|_| {
         for robot in &mut robots {
             robot.step(size);
         }
 
         let counts = robots.iter().map(|r| map_fn(&r.position)).counts();
-        if counts.values().max().unwrap() > &OUTLIER_THRESHOLD {
-            return second;
-        }
-    }
+        counts.values().max().unwrap() > &OUTLIER_THRESHOLD
+    })
 }
 
 fn robots_count(robots: &[Robot]) -> HashMap<IVec2, usize> {
@@ -163,14 +178,62 @@ fn print_map(robots: &[Robot], size: IVec2) {
     });
 }
 
+/// Shows every second with an outlier in the columns or rows until the tree appears
+fn animate_outliers(robots: &[Robot], size: IVec2, outliers: (i32, i32), tree_second: i32) {
+    for second in 0..=tree_second {
+        if (second - outliers.0) % size.x != 0 && (second - outliers.1) % size.y != 0 {
+            continue;
+        }
+
+        let mut robots = robots.to_vec();
+        for robot in &mut robots {
+            robot.steps(second, size);
+        }
+        let count = robots_count(&robots);
+        animation::frame_area_with_title(
+            format!("Second {}", second),
+            (IVec2::ZERO, size - 1),
+            |p| if count.contains_key(&p) { "#" } else { " " }.to_string(),
+        );
+    }
+}
+
+/// Saves the tree as PNG and SVG and an animation of the seconds before it appears
+fn save_visualisations(robots: &[Robot], size: IVec2, tree_second: i32) {
+    let image_at = |second: i32| {
+        let mut robots = robots.to_vec();
+        for robot in &mut robots {
+            robot.steps(second, size);
+        }
+        let count = robots_count(&robots);
+        Image::from_area((IVec2::ZERO, size - 1), |p| {
+            if count.contains_key(&p) {
+                GREEN
+            } else {
+                BLACK
+            }
+        })
+    };
+
+    let tree = image_at(tree_second);
+    viz::save_png("tree", &tree.scaled(VIZ_SCALE));
+    viz::save_svg("tree", &tree, VIZ_SCALE);
+
+    let mut animation = Animation::new(10);
+    for second in (tree_second - VIZ_SECONDS).max(0)..=tree_second {
+        animation.push(image_at(second).scaled(VIZ_SCALE));
+    }
+    viz::save_gif("tree", &animation);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::input;
+    use crate::{input, params};
 
     #[test]
     fn test_part1_example() {
-        let solution = Solution::with_input(input!(example));
+        let solution = Solution::with_params(input!(example), &params!(size = "11x7"));
         assert_eq!(solution.part1(), "12");
     }
 
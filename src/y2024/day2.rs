@@ -1,13 +1,10 @@
-use std::io::{self, BufRead};
+use nom::character::complete::space1;
 
-use nom::{
-    character::complete::{self, newline, space1},
-    multi::separated_list1,
-    IResult,
+use crate::{
+    aoc::day::{DayParser, DaySolution},
+    common::parsing::{lines, numbers, parse_input},
 };
 
-use crate::aoc::day::{DayParser, DaySolution};
-
 pub const TITLE: &str = "Red-Nosed Reports";
 
 pub struct Solution {
@@ -15,21 +12,12 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
-        let (_, reports) = reports(input.as_str()).unwrap();
+    fn with_input(input: &str) -> Self {
+        let reports = parse_input(lines(numbers(space1)), input);
         Self { reports }
     }
 }
 
-fn reports(input: &str) -> IResult<&str, Vec<Vec<u8>>> {
-    separated_list1(newline, report)(input)
-}
-
-fn report(input: &str) -> IResult<&str, Vec<u8>> {
-    separated_list1(space1, complete::u8)(input)
-}
-
 impl DaySolution for Solution {
     fn part1(&self) -> String {
         let safe_reports = self.reports.iter().filter(|r| is_safe(r));
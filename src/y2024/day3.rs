@@ -1,5 +1,3 @@
-use std::io::{self, BufRead};
-
 use regex::Regex;
 
 use crate::aoc::day::{DayParser, DaySolution};
@@ -19,11 +17,10 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
+    fn with_input(input: &str) -> Self {
         let regex = Regex::new(REGEX).unwrap();
         let instructions = regex
-            .captures_iter(input.as_str())
+            .captures_iter(input)
             .map(|cap| match &cap[1] {
                 "do()" => Instruction::Do,
                 "don't()" => Instruction::Dont,
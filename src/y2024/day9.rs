@@ -1,7 +1,4 @@
-use std::{
-    collections::VecDeque,
-    io::{self, BufRead},
-};
+use std::collections::VecDeque;
 
 use crate::aoc::day::{DayParser, DaySolution};
 
@@ -25,8 +22,7 @@ pub struct Solution {
 }
 
 impl DayParser for Solution {
-    fn with_input(input: &mut dyn BufRead) -> Self {
-        let input = io::read_to_string(input).unwrap();
+    fn with_input(input: &str) -> Self {
         let input = input
             .trim()
             .chars()